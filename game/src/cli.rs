@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+use argh::FromArgs;
+use crossterm::Result;
+use game_actions::config::Config;
+use game_actions::config::glyph_set::GlyphSet;
+use game_actions::config::palette_name::PaletteName;
+use game_actions::config::theme_name::ThemeName;
+use game_actions::game_context::GameContext;
+use game_actions::start_options::StartOptions;
+use crate::leaderboard_transfer::LeaderboardTransfer;
+
+const DEFAULT_GAME: &str = "minesweeper";
+
+#[derive(FromArgs, Debug)]
+/// Minesweeper and snake in the terminal.
+pub struct Cli {
+    /// game to start straight away: minesweeper or snake
+    #[argh(option, short = 'g')]
+    pub game: Option<String>,
+
+    /// difficulty of the game: easy, medium or hard (small and large also work)
+    #[argh(option, short = 'd')]
+    pub difficulty: Option<String>,
+
+    /// board width of a custom game, given together with --height, and --mines for minesweeper
+    #[argh(option)]
+    pub width: Option<i32>,
+
+    /// board height of a custom game, given together with --width, and --mines for minesweeper
+    #[argh(option)]
+    pub height: Option<i32>,
+
+    /// number of mines in a custom minesweeper game
+    #[argh(option)]
+    pub mines: Option<i32>,
+
+    /// only deal minesweeper boards that can be solved without guessing
+    #[argh(switch)]
+    pub no_guess: bool,
+
+    /// seed for the board, the same seed gives the same board
+    #[argh(option)]
+    pub seed: Option<u64>,
+
+    /// glyphs to draw minesweeper with: emoji, unicode or ascii
+    #[argh(option)]
+    pub glyph_set: Option<GlyphSet>,
+
+    /// theme to start with: dark, light or high-contrast
+    #[argh(option)]
+    pub theme: Option<ThemeName>,
+
+    /// colours of the minesweeper numbers: theme, deuteranopia, protanopia, tritanopia or monochrome
+    #[argh(option)]
+    pub palette: Option<PaletteName>,
+
+    /// log level, such as error, warn, info, debug or trace
+    #[argh(option, default = "String::from(\"info\")")]
+    pub log_level: String,
+
+    /// file to write the log to instead of terminal_games.log in the data directory
+    #[argh(option)]
+    pub log_file: Option<PathBuf>,
+
+    /// config file to use instead of $XDG_CONFIG_HOME/terminal_games/config.yaml
+    #[argh(option)]
+    pub config: Option<PathBuf>,
+
+    /// directory to keep scores and replays in instead of $XDG_DATA_HOME/terminal_games
+    #[argh(option)]
+    pub data_dir: Option<PathBuf>,
+
+    /// write the leaderboard of --game to a file and exit
+    #[argh(option)]
+    pub export_leaderboard: Option<PathBuf>,
+
+    /// merge the leaderboard in a file into the one of --game and exit
+    #[argh(option)]
+    pub import_leaderboard: Option<PathBuf>,
+
+    /// print the version and exit
+    #[argh(switch, short = 'V')]
+    pub version: bool,
+}
+
+impl Cli {
+    // A config file given on the command line has to exist, the default one is optional.
+    // Options given on the command line win over the config file.
+    pub fn load_config(&self) -> Result<Config> {
+        let mut config = match &self.config {
+            Some(path) => Config::load(path)?,
+            None => Config::load_default()?,
+        };
+        if let Some(glyph_set) = self.glyph_set {
+            config.minesweeper.glyph_set = glyph_set;
+        }
+        if let Some(theme) = self.theme {
+            config.theme = theme;
+        }
+        if let Some(palette) = self.palette {
+            config.palette = palette;
+        }
+        if self.no_guess {
+            config.minesweeper.no_guess = true;
+        }
+        Ok(config)
+    }
+
+    pub fn get_context(&self, config: Config) -> GameContext {
+        GameContext {
+            seed: self.seed,
+            data_dir: self.data_dir.clone(),
+            config,
+        }
+    }
+
+    // The game to open on start, if any was asked for. The game itself works out which variant
+    // the options stand for.
+    pub fn get_start_game(&self) -> std::result::Result<Option<(String, StartOptions)>, String> {
+        let size = match (self.width, self.height) {
+            (Some(width), Some(height)) => Some((width, height)),
+            (None, None) if self.mines.is_some() => return Err("--mines needs --width and --height".to_string()),
+            (None, None) => None,
+            _ => return Err("--width and --height have to be given together".to_string()),
+        };
+        if self.difficulty.is_some() && size.is_some() {
+            return Err("Use either --difficulty or a custom size".to_string());
+        }
+        Ok(match (&self.game, &self.difficulty, size) {
+            (None, None, None) => None,
+            (game, difficulty, size) => Some((
+                game.clone().unwrap_or_else(|| DEFAULT_GAME.to_string()),
+                StartOptions { difficulty: difficulty.clone(), size, mines: self.mines },
+            )),
+        })
+    }
+
+    // The leaderboard to export or import instead of starting the games, if any was asked for.
+    pub fn get_leaderboard_transfer(&self) -> std::result::Result<Option<(String, LeaderboardTransfer)>, String> {
+        let transfer = match (&self.export_leaderboard, &self.import_leaderboard) {
+            (Some(_), Some(_)) => return Err("Use either --export-leaderboard or --import-leaderboard".to_string()),
+            (Some(path), None) => LeaderboardTransfer::Export(path.clone()),
+            (None, Some(path)) => LeaderboardTransfer::Import(path.clone()),
+            (None, None) => return Ok(None),
+        };
+        Ok(Some((self.game.clone().unwrap_or_else(|| DEFAULT_GAME.to_string()), transfer)))
+    }
+}
@@ -1,18 +1,37 @@
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture, Event, KeyCode, MouseButton, MouseEvent, MouseEventKind, poll, read};
-use crossterm::{execute, Result, terminal};
-use std::io::stdout;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind, poll, read};
+use crossterm::{ErrorKind, Result, terminal};
 use std::time::{Duration, Instant};
-use log::info;
+use log::{error, info};
 use game_actions::click_action::ClickAction;
-use game_actions::game_type::GameType;
-use minesweeper_tui_game_view::game_view::GameView;
+use game_actions::data_store::DataStore;
+use game_actions::game_context::GameContext;
+use game_actions::game_descriptor::GameDescriptor;
+use game_actions::game_registry::GameRegistry;
+use game_actions::config::palette_name::PaletteName;
+use game_actions::config::theme_name::ThemeName;
+use game_actions::game_variant::GameVariant;
+use game_actions::start_options::StartOptions;
+use minesweeper_tui_game_view::minesweeper_descriptor::MinesweeperDescriptor;
+use snake_tui_game_view::snake_descriptor::SnakeDescriptor;
 use tui::screen::point::Point;
+use tui::screen::style::Style;
+use crate::leaderboard_transfer::LeaderboardTransfer;
+use crate::settings_view::SettingsView;
+use crate::terminal_guard::TerminalGuard;
 use tui::screen::Screen;
 use tui::screen::window::border_style::BorderStyle;
+use tui::screen::window::component::Component;
 use tui::screen::window::Window;
 use tui::screen::window::button::ButtonComponent;
+use tui::screen::window::key_action::KeyAction;
+use tui::screen::window::label::LabelComponent;
 use tui::screen::window::mouse_action::MouseAction::{Double, Drag, Left, Middle, Move, Right};
 
+const MENU_TOP: i32 = 5;
+const MENU_LEFT: i32 = 16;
+const MENU_ROW_HEIGHT: i32 = 4;
+const MENU_COLUMN_WIDTH: i32 = 10;
+
 #[derive(PartialEq)]
 enum GameRunState {
     KeepRunning,
@@ -21,15 +40,21 @@ enum GameRunState {
 
 struct State {
     screen: Screen<ClickAction>,
+    registry: GameRegistry,
+    context: GameContext,
     last_left_click: Point,
     last_left_click_time: Instant
 }
 
 impl State {
-    fn new() -> Result<Self> {
+    fn new(registry: GameRegistry, context: GameContext) -> Result<Self> {
         let (width, height) = terminal::size()?;
+        let mut screen = Screen::new(width as i32, height as i32);
+        screen.set_theme(context.config.get_theme(context.config.theme));
         let mut state = State {
-            screen: Screen::new(width as i32, height as i32),
+            screen,
+            registry,
+            context,
             last_left_click: (0,0).into(),
             last_left_click_time: Instant::now()
         };
@@ -43,51 +68,119 @@ impl State {
             false
         ))?;
         state.screen.add(Window::new(
-            (10, 5).into(),
+            (10,0).into(),
             99,
-            Box::from(ButtonComponent::new(Box::from("Easy"), (6, 1).into(), ClickAction::Minesweeper(GameType::Small))),
-            BorderStyle::Single,
-            Box::default(),
-            false,
-            false
-        ))?;
-        state.screen.add(Window::new(
-            (20, 5).into(),
-            98,
-            Box::from(ButtonComponent::new(Box::from("Medium"), (6, 1).into(), ClickAction::Minesweeper(GameType::Medium))),
-            BorderStyle::Single,
-            Box::default(),
-            false,
-            false
-        ))?;
-        state.screen.add(Window::new(
-            (30, 5).into(),
-            97,
-            Box::from(ButtonComponent::new(Box::from("Hard"), (6, 1).into(), ClickAction::Minesweeper(GameType::Large))),
-            BorderStyle::Single,
+            Box::from(ButtonComponent::new(Box::from("Settings"), (10,1).into(), ClickAction::OpenSettings)),
+            BorderStyle::Dotted,
             Box::default(),
             false,
             false
         ))?;
 
-        return Ok(state);
+        // One row per game: its name followed by a button for each variant.
+        let mut z = 99;
+        for (row, game) in state.registry.get_games().iter().enumerate() {
+            let y = MENU_TOP + row as i32 * MENU_ROW_HEIGHT;
+            state.screen.add(Window::new(
+                (2, y + 1).into(),
+                z,
+                Box::from(LabelComponent::new(Box::from(game.get_name()))),
+                BorderStyle::None,
+                Box::default(),
+                false,
+                false
+            ))?;
+            for (column, variant) in game.get_variants().into_iter().enumerate() {
+                z -= 1;
+                state.screen.add(Window::new(
+                    (MENU_LEFT + column as i32 * MENU_COLUMN_WIDTH, y).into(),
+                    z,
+                    Box::from(ButtonComponent::new(
+                        Box::from(game.get_variant_label(&variant)),
+                        (6, 1).into(),
+                        ClickAction::Open(game.get_id(), variant)
+                    )),
+                    BorderStyle::Single,
+                    Box::default(),
+                    false,
+                    false
+                ))?;
+            }
+            z -= 1;
+        }
+
+        Ok(state)
+    }
+
+    fn open(&mut self, game_id: &str, variant: GameVariant) -> Result<()> {
+        let game = match self.registry.get(game_id) {
+            Some(game) => game,
+            None => Err(ErrorKind::other(format!("Unknown game {}", game_id)))?,
+        };
+        info!("Opening {:?} of {}", variant, game.get_name());
+        let component = game.create(&variant, &self.context)?;
+        let title = game.get_window_title(&variant);
+        self.add_game_window(component, title)
+    }
+
+    fn add_game_window(&mut self, component: Box<dyn Component<ClickAction>>, title: String) -> Result<()> {
+        let location = (5, MENU_TOP + self.registry.get_games().len() as i32 * MENU_ROW_HEIGHT).into();
+        self.screen.add(Window::new(
+            location,
+            0,
+            component,
+            BorderStyle::Double,
+            Box::from(title),
+            true,
+            true
+        ))
+    }
+
+    fn show_error(&mut self, message: String) -> Result<()> {
+        error!("{}", message);
+        let style = self.screen.get_theme().error;
+        self.show_message("Error", message, style)
+    }
+
+    fn show_notice(&mut self, message: String) -> Result<()> {
+        info!("{}", message);
+        self.show_message("Notice", message, Style::default())
+    }
+
+    fn show_message(&mut self, title: &str, message: String, style: Style) -> Result<()> {
+        let label = LabelComponent::new_with_style(Box::from(message), style);
+        self.screen.add(Window::new(
+            (5, MENU_TOP).into(),
+            0,
+            Box::from(label),
+            BorderStyle::Double,
+            Box::from(title),
+            true,
+            true
+        ))
+    }
+
+    fn set_theme(&mut self, name: ThemeName) {
+        info!("Switching to the {} theme", name);
+        self.context.config.theme = name;
+        self.screen.set_theme(self.context.config.get_theme(name));
+    }
+
+    fn set_palette(&mut self, palette: PaletteName) {
+        info!("Switching to the {} palette", palette);
+        let config = &mut self.context.config;
+        config.palette = palette;
+        self.screen.set_theme(config.get_theme(config.theme));
     }
 
     fn handle_click_actions(&mut self, click_actions: Vec<ClickAction>) -> Result<GameRunState>{
         let mut windows_to_remove = vec![];
         for action in click_actions {
             match action {
-                ClickAction::Minesweeper(game_type) => {
-                    info!("Starting new {:?} game of minesweeper", game_type);
-                    self.screen.add(Window::new(
-                        (5, 10).into(),
-                        0,
-                        Box::from(GameView::new(game_type)),
-                        BorderStyle::Double,
-                        Box::from(game_type_to_minesweeper_string(game_type)),
-                        true,
-                        true
-                    ))?;
+                ClickAction::Open(game_id, variant) => {
+                    if let Err(e) = self.open(game_id, variant) {
+                        self.show_error(format!("Could not open {}: {}", game_id, e))?;
+                    }
                 }
                 ClickAction::Quit => {
                     info!("Quitting Application");
@@ -96,6 +189,14 @@ impl State {
                 ClickAction::Close(window_id) => {
                     windows_to_remove.push(window_id);
                 }
+                ClickAction::ShowError(message) => self.show_error(message)?,
+                ClickAction::ShowNotice(message) => self.show_notice(message)?,
+                ClickAction::OpenSettings => {
+                    let settings = SettingsView::new(self.context.config.palette);
+                    self.add_game_window(Box::from(settings), "Settings".to_string())?;
+                }
+                ClickAction::SetTheme(name) => self.set_theme(name),
+                ClickAction::SetPalette(palette) => self.set_palette(palette),
                 ClickAction::Refresh => {
                     info!("Screen refresh requested");
                     self.screen.refresh()?;
@@ -120,7 +221,7 @@ impl State {
                     MouseButton::Left => {
                         let point = (x,y).into();
                         if point == self.last_left_click &&
-                            self.last_left_click_time.elapsed().as_millis() <= 500 {
+                            self.last_left_click_time.elapsed().as_millis() <= self.context.config.input.double_click_millis as u128 {
                             Some(Double(point))
                         }
                         else{
@@ -133,30 +234,20 @@ impl State {
                     MouseButton::Middle => Some(Middle((x, y).into()))
                 }
             },
-            MouseEventKind::Up(button) => {
-                // Some terminals show "Up" instead of Move.
-                match button {
-                    MouseButton::Left => Some(Move((x, y).into())),
-                    _ => None
-                }
-            },
+            // Some terminals show "Up" instead of Move.
+            MouseEventKind::Up(MouseButton::Left) => Some(Move((x, y).into())),
             MouseEventKind::Moved => {
                 Some(Move((x, y).into()))
             },
-            MouseEventKind::Drag(button) => {
-                match button {
-                    MouseButton::Left => {
-                        let (_, last_y) = self.last_left_click.into();
-                        if y == 0 && last_y != 1 {
-                            y = last_y;
-                        }
-                        let to = (x, y).into();
-                        let from = self.last_left_click;
-                        self.last_left_click = to;
-                        Some(Drag(from, to))
-                    },
-                    _ => None
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let (_, last_y) = self.last_left_click.into();
+                if y == 0 && last_y != 1 {
+                    y = last_y;
                 }
+                let to = (x, y).into();
+                let from = self.last_left_click;
+                self.last_left_click = to;
+                Some(Drag(from, to))
             },
             _ => None
         };
@@ -167,55 +258,225 @@ impl State {
         Ok(GameRunState::KeepRunning)
     }
 
+    fn handle_key(&mut self, event: KeyEvent) -> Result<GameRunState> {
+        if event.kind == KeyEventKind::Release {
+            return Ok(GameRunState::KeepRunning);
+        }
+        let some_key = match event.code {
+            KeyCode::Char(c) => Some(KeyAction::Char(c)),
+            KeyCode::Up => Some(KeyAction::Up),
+            KeyCode::Down => Some(KeyAction::Down),
+            KeyCode::Left => Some(KeyAction::Left),
+            KeyCode::Right => Some(KeyAction::Right),
+            KeyCode::Enter => Some(KeyAction::Enter),
+            KeyCode::Esc => Some(KeyAction::Escape),
+            KeyCode::Backspace => Some(KeyAction::Backspace),
+            KeyCode::Delete => Some(KeyAction::Delete),
+            KeyCode::Tab => Some(KeyAction::Tab),
+            KeyCode::BackTab => Some(KeyAction::BackTab),
+            KeyCode::Home => Some(KeyAction::Home),
+            KeyCode::End => Some(KeyAction::End),
+            _ => None
+        };
+        if let Some(key) = some_key {
+            match self.screen.handle_key(key)? {
+                Some(click_actions) => return self.handle_click_actions(click_actions),
+                // Quit and theme keys only work when the focused window has no use for them.
+                None if self.context.config.input.quit.contains(&key) => {
+                    info!("Quitting Application");
+                    return Ok(GameRunState::Close);
+                }
+                None if self.context.config.input.theme.contains(&key) => self.set_theme(self.context.config.theme.next()),
+                None => {}
+            }
+        }
+        Ok(GameRunState::KeepRunning)
+    }
+
     fn game_loop(&mut self) -> Result<()> {
         // Start game loop.
         loop {
+            let tick_actions = self.screen.tick(Instant::now())?;
+            if self.handle_click_actions(tick_actions)? == GameRunState::Close {
+                return Ok(());
+            }
             self.screen.draw()?;
-            if let Ok(ready) = poll(Duration::from_millis(30)) {
-                if ready {
-                    match read()? {
-                        Event::Mouse(event) => {
-                            if self.handle_mouse_click(event)? == GameRunState::Close {
-                                return Ok(());
-                            }
-                        },
-                        Event::Resize(width, height) =>
-                            self.screen.change_size(width as i32, height as i32)?,
-                        Event::Key(key) => match key.code {
-                            KeyCode::Char(char) => {
-                                if char == 'q' {
-                                    return Ok(());
-                                }
-                            }
-                            _ => {}
-                        },
-                        _ => {}
+            // Wait for the next event, but no longer than until the next tick is due.
+            let poll_interval = match self.context.config.input.poll_millis {
+                0 => None,
+                millis => Some(Duration::from_millis(millis)),
+            };
+            let ready = match (self.screen.get_time_until_tick(Instant::now()), poll_interval) {
+                (Some(timeout), Some(interval)) => poll(timeout.min(interval))?,
+                (Some(timeout), None) | (None, Some(timeout)) => poll(timeout)?,
+                (None, None) => true,
+            };
+            if ready {
+                let run_state = match read()? {
+                    Event::Mouse(event) => self.handle_mouse_click(event)?,
+                    Event::Resize(width, height) => {
+                        self.screen.change_size(width as i32, height as i32)?;
+                        GameRunState::KeepRunning
                     }
+                    Event::Key(key) => self.handle_key(key)?,
+                    _ => GameRunState::KeepRunning,
+                };
+                if run_state == GameRunState::Close {
+                    return Ok(());
                 }
             }
         }
     }
 }
 
-pub fn start() -> Result<()> {
-    let mut stdout = stdout();
-    execute!(stdout, terminal::Clear(terminal::ClearType::All))?;
-    terminal::enable_raw_mode()?;
-    execute!(stdout, EnableMouseCapture)?;
+// Opens start_game straight away when given. It is created before the terminal is taken
+// over, so a bad game or size is reported as a normal error.
+pub fn start(context: GameContext, start_game: Option<(String, StartOptions)>) -> Result<()> {
+    let registry = create_registry();
+    let start_window = match start_game {
+        Some((game_id, options)) => Some(create_start_window(&registry, &context, &game_id, &options)?),
+        None => None,
+    };
+
+    // Older versions kept their files next to the executable.
+    if context.data_dir.is_none() {
+        migrate_data_files(&registry, &context);
+    }
+
+    let _terminal_guard = TerminalGuard::new()?;
+    let mut state = State::new(registry, context)?;
+    if let Some((component, title)) = start_window {
+        state.add_game_window(component, title)?;
+    }
+    state.game_loop()
+}
+
+// Exports or imports a leaderboard without taking over the terminal.
+pub fn transfer_leaderboard(context: GameContext, game_id: &str, transfer: LeaderboardTransfer) -> Result<()> {
+    let registry = create_registry();
+    let game = get_game(&registry, game_id)?;
+    if context.data_dir.is_none() {
+        migrate_data_files(&registry, &context);
+    }
+    match transfer {
+        LeaderboardTransfer::Export(path) => {
+            let count = game.export_leaderboard(&context, &path)?;
+            println!("Exported {} leaderboard entries to {}", count, path.display());
+        }
+        LeaderboardTransfer::Import(path) => {
+            let count = game.import_leaderboard(&context, &path)?;
+            println!("Imported {} new leaderboard entries from {}", count, path.display());
+        }
+    }
+    Ok(())
+}
+
+// The game window asked for on the command line, with its title. Options the game cannot use
+// are an error before the terminal is taken over.
+fn create_start_window(
+    registry: &GameRegistry,
+    context: &GameContext,
+    game_id: &str,
+    options: &StartOptions,
+) -> Result<(Box<dyn Component<ClickAction>>, String)> {
+    let game = get_game(registry, game_id)?;
+    let variant = game.get_start_variant(options, context).map_err(ErrorKind::other)?;
+    Ok((game.create(&variant, context)?, game.get_window_title(&variant)))
+}
 
-    let mut state: State = State::new()?;
-    let result = state.game_loop();
+fn create_registry() -> GameRegistry {
+    let mut registry = GameRegistry::new();
+    registry.register(Box::from(MinesweeperDescriptor));
+    registry.register(Box::from(SnakeDescriptor));
+    registry
+}
 
-    execute!(stdout, DisableMouseCapture)?;
-    terminal::disable_raw_mode()?;
+fn get_game<'a>(registry: &'a GameRegistry, game_id: &str) -> Result<&'a dyn GameDescriptor> {
+    registry.get(game_id).ok_or_else(|| ErrorKind::other(format!("Unknown game {}, expected one of: {}", game_id,
+        registry.get_games().iter().map(|g| g.get_id()).collect::<Vec<_>>().join(", "))))
+}
 
-    return result;
+fn migrate_data_files(registry: &GameRegistry, context: &GameContext) {
+    let old_dir = match DataStore::get_exe_dir() {
+        Some(old_dir) => old_dir,
+        None => return,
+    };
+    let data_store = context.get_data_store();
+    for file_name in registry.get_games().iter().flat_map(|g| g.get_data_files()) {
+        match data_store.migrate(&old_dir, file_name) {
+            Ok(true) => info!("Moved {} to {}", file_name, data_store.get_dir().display()),
+            Ok(false) => {}
+            Err(e) => error!("{}", e),
+        }
+    }
 }
 
-fn game_type_to_minesweeper_string(game_type: GameType) -> String {
-    return match game_type {
-        GameType::Small => "Easy",
-        GameType::Medium => "Medium",
-        GameType::Large => "Hard"
-    }.to_string();
+#[cfg(test)]
+mod tests {
+    use argh::FromArgs;
+    use game_actions::config::Config;
+    use crate::cli::Cli;
+    use super::*;
+
+    // The title of the window the arguments open, or the error they give.
+    fn start(args: &[&str]) -> std::result::Result<String, String> {
+        let cli = Cli::from_args(&["terminal_games"], args).unwrap();
+        let (game_id, options) = cli.get_start_game()?.unwrap();
+        let context = cli.get_context(Config::default());
+        match create_start_window(&create_registry(), &context, &game_id, &options) {
+            Ok((_, title)) => Ok(title),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    #[test]
+    fn named_and_custom_games_open() {
+        assert_eq!(start(&["--difficulty", "hard"]), Ok("Hard".to_string()));
+        assert_eq!(start(&["--width", "20", "--height", "10", "--mines", "30"]), Ok("Custom 20x10 (30)".to_string()));
+        assert_eq!(start(&["--game", "snake", "--width", "20", "--height", "10"]), Ok("Snake 20x10".to_string()));
+    }
+
+    #[test]
+    fn unknown_game_is_an_error() {
+        assert_eq!(start(&["--game", "tetris"]), Err("Unknown game tetris, expected one of: minesweeper, snake".to_string()));
+    }
+
+    #[test]
+    fn custom_minesweeper_board_needs_mines() {
+        assert_eq!(
+            start(&["--width", "20", "--height", "10"]),
+            Err("A custom minesweeper board needs --mines as well as --width and --height".to_string())
+        );
+    }
+
+    #[test]
+    fn mines_need_a_custom_size() {
+        assert_eq!(start(&["--difficulty", "easy", "--mines", "10"]), Err("--mines needs --width and --height".to_string()));
+        assert_eq!(start(&["--game", "snake", "--mines", "10"]), Err("--mines needs --width and --height".to_string()));
+    }
+
+    #[test]
+    fn snake_has_no_mines() {
+        assert_eq!(
+            start(&["--game", "snake", "--width", "20", "--height", "10", "--mines", "10"]),
+            Err("Snake has no mines, so --mines cannot be used with it".to_string())
+        );
+    }
+
+    #[test]
+    fn sizes_out_of_range_are_errors() {
+        assert_eq!(start(&["--width", "100", "--height", "10", "--mines", "30"]), Err("Width must be 11-60".to_string()));
+        assert_eq!(start(&["--width", "20", "--height", "2", "--mines", "5"]), Err("Height must be 4-40".to_string()));
+        assert_eq!(start(&["--game", "snake", "--width", "8", "--height", "10"]), Err("Width must be 12-60".to_string()));
+        assert_eq!(start(&["--game", "snake", "--width", "20", "--height", "50"]), Err("Height must be at most 40".to_string()));
+    }
+
+    #[test]
+    fn size_is_given_as_a_whole() {
+        assert_eq!(start(&["--width", "20"]), Err("--width and --height have to be given together".to_string()));
+        assert_eq!(
+            start(&["--difficulty", "easy", "--width", "20", "--height", "10", "--mines", "30"]),
+            Err("Use either --difficulty or a custom size".to_string())
+        );
+    }
 }
@@ -0,0 +1,8 @@
+use std::path::PathBuf;
+
+// Moving a leaderboard between machines, asked for on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeaderboardTransfer {
+    Export(PathBuf),
+    Import(PathBuf),
+}
@@ -1,17 +1,54 @@
+mod cli;
 mod game;
+mod leaderboard_transfer;
+mod settings_view;
+mod terminal_guard;
 
+use std::process::exit;
 use crossterm::{ErrorKind, Result};
 use flexi_logger::{FileSpec, FlexiLoggerError, Logger};
 use log::info;
-use std::io;
+use crate::cli::Cli;
+use crate::terminal_guard::TerminalGuard;
 
 fn main() -> Result<()> {
-    let logger_result = Logger::try_with_str("info");
+    let cli: Cli = argh::from_env();
+    if cli.version {
+        println!("terminal_games {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+    let config = match cli.load_config() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
+    let start_game = match cli.get_start_game() {
+        Ok(start_game) => start_game,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
+
+    let leaderboard_transfer = match cli.get_leaderboard_transfer() {
+        Ok(leaderboard_transfer) => leaderboard_transfer,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
+
+    let context = cli.get_context(config);
+    let logger_result = Logger::try_with_str(&cli.log_level);
     match logger_result {
         Ok(logger) => {
-            let start_result = logger
-                .log_to_file(FileSpec::default().suppress_timestamp())
-                .start();
+            let file_spec = match &cli.log_file {
+                Some(log_file) => FileSpec::try_from(log_file).or_else(handle_flexi_logger_error)?,
+                None => FileSpec::default().directory(context.get_data_dir()).suppress_timestamp(),
+            };
+            let start_result = logger.log_to_file(file_spec).start();
             if let Err(e) = start_result {
                 handle_flexi_logger_error(e)?;
             }
@@ -20,9 +57,18 @@ fn main() -> Result<()> {
     }
 
     info!("*** Terminal Games v{} ***", env!("CARGO_PKG_VERSION"));
-    game::start()
+    TerminalGuard::set_panic_hook();
+    let result = match leaderboard_transfer {
+        Some((game_id, transfer)) => game::transfer_leaderboard(context, &game_id, transfer),
+        None => game::start(context, start_game),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        exit(1);
+    }
+    Ok(())
 }
 
-fn handle_flexi_logger_error(error: FlexiLoggerError) -> Result<()> {
-    Err(ErrorKind::new(io::ErrorKind::Other, error))
+fn handle_flexi_logger_error<T>(error: FlexiLoggerError) -> Result<T> {
+    Err(ErrorKind::other(error))
 }
@@ -0,0 +1,140 @@
+use crossterm::Result;
+use game_actions::click_action::ClickAction;
+use game_actions::config::palette_name::PaletteName;
+use game_actions::config::theme_name::ThemeName;
+use tui::screen::dimension::Dimension;
+use tui::screen::style::Style;
+use tui::screen::text;
+use tui::screen::theme::Theme;
+use tui::screen::window::component::Component;
+use tui::screen::window::key_action::KeyAction;
+use tui::screen::window::mouse_action::MouseAction;
+use tui::screen::window::update_element::UpdateElement;
+use uuid::Uuid;
+
+const VIEW_WIDTH: i32 = 27;
+const LABELS: [&str; 2] = ["Theme", "Numbers"];
+const THEME_ROW: usize = 0;
+const PREVIOUS_X: i32 = 9;
+const VALUE_WIDTH: usize = 14;
+const PREVIEW_ROW: i32 = 3;
+
+// Picks the theme and the palette of the numbers while playing. Left clicking a row picks the
+// next value and right clicking or clicking < the previous one. With the keyboard, Up and Down
+// pick a row and Left and Right change its value.
+pub struct SettingsView {
+    id: Uuid,
+    // Row picked with the keyboard.
+    selected: usize,
+    palette: PaletteName,
+    // The theme name is read from the theme, as the theme key can change it too.
+    theme: Theme,
+    changed: bool,
+}
+
+impl SettingsView {
+    pub fn new(palette: PaletteName) -> Self {
+        SettingsView {
+            id: Uuid::new_v4(),
+            selected: 0,
+            palette,
+            theme: Theme::default(),
+            changed: true,
+        }
+    }
+
+    fn change(&mut self, row: usize, forward: bool) -> Vec<ClickAction> {
+        if row == THEME_ROW {
+            let name = self.theme.name.parse().unwrap_or(ThemeName::Dark);
+            return vec![ClickAction::SetTheme(if forward { name.next() } else { name.previous() })];
+        }
+        self.palette = if forward { self.palette.next() } else { self.palette.previous() };
+        self.changed = true;
+        vec![ClickAction::SetPalette(self.palette)]
+    }
+
+    fn get_row_style(&self, row: usize) -> Style {
+        match self.selected == row {
+            true => self.theme.selected,
+            false => Style::default(),
+        }
+    }
+}
+
+impl Component<ClickAction> for SettingsView {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        (VIEW_WIDTH, PREVIEW_ROW + 1).into()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        let mut updates = vec![];
+        let values = [self.theme.name.to_string(), self.palette.to_string()];
+        for (row, (label, value)) in LABELS.iter().zip(values.iter()).enumerate() {
+            let line = format!("{:<w$}< {:^v$} >", label, value, w = PREVIOUS_X as usize, v = VALUE_WIDTH);
+            updates.append(&mut text::layout(&text::fit(&line, VIEW_WIDTH), (0, row as i32).into(), VIEW_WIDTH, self.get_row_style(row)));
+        }
+        // The numbers as the board draws them.
+        updates.append(&mut text::layout(&text::fit("Preview", VIEW_WIDTH), (0, PREVIEW_ROW).into(), VIEW_WIDTH, Style::default()));
+        for (i, style) in self.theme.palette.iter().enumerate() {
+            updates.push(UpdateElement {
+                point: (PREVIOUS_X + 2 + i as i32 * 2, PREVIEW_ROW).into(),
+                value: char::from_digit(i as u32 + 1, 10).unwrap_or(' ').to_string(),
+                style: *style,
+            });
+        }
+        self.changed = false;
+        Ok(updates)
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        if self.changed {
+            self.get_state()
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    fn handle_click(&mut self, click: MouseAction) -> Result<Vec<ClickAction>> {
+        let point = click.to_point();
+        if !(0..LABELS.len() as i32).contains(&point.y) {
+            return Ok(vec![]);
+        }
+        let row = point.y as usize;
+        let forward = match click {
+            MouseAction::Left(_) => point.x != PREVIOUS_X,
+            MouseAction::Right(_) => false,
+            _ => return Ok(vec![]),
+        };
+        self.selected = row;
+        self.changed = true;
+        Ok(self.change(row, forward))
+    }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<ClickAction>>> {
+        let actions = match key {
+            KeyAction::Up => {
+                self.selected = (self.selected + LABELS.len() - 1) % LABELS.len();
+                vec![]
+            }
+            KeyAction::Down => {
+                self.selected = (self.selected + 1) % LABELS.len();
+                vec![]
+            }
+            KeyAction::Left => self.change(self.selected, false),
+            KeyAction::Right | KeyAction::Enter | KeyAction::Char(' ') => self.change(self.selected, true),
+            KeyAction::Escape => vec![ClickAction::Close(self.id)],
+            _ => return Ok(None),
+        };
+        self.changed = true;
+        Ok(Some(actions))
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+        self.changed = true;
+    }
+}
@@ -0,0 +1,49 @@
+use std::io::stdout;
+use std::panic;
+use crossterm::cursor::Show;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::terminal::{self, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, Result};
+use log::error;
+
+// Takes over the terminal for as long as it lives: the alternate screen, raw mode and mouse
+// capture. Dropping it gives the terminal back, also when the game loop returns an error.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn new() -> Result<Self> {
+        // Created first, so whatever was already changed is undone when a later step fails.
+        let guard = TerminalGuard;
+        execute!(stdout(), EnterAlternateScreen, terminal::Clear(ClearType::All))?;
+        terminal::enable_raw_mode()?;
+        execute!(stdout(), EnableMouseCapture)?;
+        Ok(guard)
+    }
+
+    // Logs a panic and gives the terminal back before the default hook prints it, so the message
+    // ends up on the normal screen instead of the alternate one. The guard is dropped again while
+    // unwinding, which does no harm.
+    pub fn set_panic_hook() {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            error!("{}", info);
+            if terminal::is_raw_mode_enabled().unwrap_or(false) {
+                restore_terminal();
+            }
+            default_hook(info);
+        }));
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+// Every step is tried even when one fails, as there is nothing better to do with the error.
+fn restore_terminal() {
+    let _ = execute!(stdout(), DisableMouseCapture);
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(stdout(), LeaveAlternateScreen, Show);
+}
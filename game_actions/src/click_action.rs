@@ -1,13 +1,24 @@
 use uuid::Uuid;
 use tui::screen::window::has_close_action::HasCloseAndRefreshActions;
-use crate::game_type::GameType;
+use crate::config::palette_name::PaletteName;
+use crate::config::theme_name::ThemeName;
+use crate::game_variant::GameVariant;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ClickAction {
-    Minesweeper(GameType),
+    // Opens a window for the game with the given GameDescriptor id.
+    Open(&'static str, GameVariant),
     Quit,
     Close(Uuid),
-    Refresh
+    Refresh,
+    // Shows the message in a window, for errors the player should know about.
+    ShowError(String),
+    // Shows the message in a window, for things the player should know that are not errors.
+    ShowNotice(String),
+    OpenSettings,
+    // Draws every window again in the theme or palette.
+    SetTheme(ThemeName),
+    SetPalette(PaletteName),
 }
 
 impl HasCloseAndRefreshActions for ClickAction {
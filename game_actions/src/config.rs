@@ -0,0 +1,226 @@
+pub mod color_config;
+pub mod glyph_set;
+pub mod input_config;
+pub mod minesweeper_config;
+pub mod minesweeper_glyphs;
+pub mod minesweeper_keys;
+pub mod palette_name;
+pub mod snake_config;
+pub mod snake_glyphs;
+pub mod snake_keys;
+pub mod theme_name;
+
+use std::env;
+use std::fs::read_to_string;
+use std::io;
+use std::path::{Path, PathBuf};
+use crossterm::{ErrorKind, Result};
+use serde::Deserialize;
+use tui::screen::theme::Theme;
+use tui::screen::window::key_action::KeyAction;
+use unicode_width::UnicodeWidthChar;
+use crate::config::color_config::ColorConfig;
+use crate::config::input_config::InputConfig;
+use crate::config::minesweeper_config::MinesweeperConfig;
+use crate::config::palette_name::PaletteName;
+use crate::config::snake_config::SnakeConfig;
+use crate::config::theme_name::ThemeName;
+
+const CONFIG_DIR_NAME: &str = "terminal_games";
+const CONFIG_FILE_NAME: &str = "config.yaml";
+// Every game reads these names, minesweeper takes small and large as easy and hard and snake
+// takes easy and hard as small and large.
+const DIFFICULTIES: [&str; 5] = ["easy", "small", "medium", "hard", "large"];
+
+// Settings read from config.yaml at start up. Every key is optional and falls back to its
+// default, see config.example.yaml for the whole schema.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub input: InputConfig,
+    // Theme to start with. The theme key of input switches themes while playing.
+    pub theme: ThemeName,
+    // Replaces the palette of the theme, for colour blindness.
+    pub palette: PaletteName,
+    pub colors: ColorConfig,
+    // Difficulty used by --game when no --difficulty is given, read by each game in its own terms.
+    pub default_difficulty: String,
+    pub minesweeper: MinesweeperConfig,
+    pub snake: SnakeConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            input: InputConfig::default(),
+            theme: ThemeName::Dark,
+            palette: PaletteName::Theme,
+            colors: ColorConfig::default(),
+            default_difficulty: "medium".to_string(),
+            minesweeper: MinesweeperConfig::default(),
+            snake: SnakeConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    // $XDG_CONFIG_HOME/terminal_games/config.yaml, or ~/.config/terminal_games/config.yaml.
+    pub fn get_default_path() -> Option<PathBuf> {
+        let config_home = match env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+        Some(config_home.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME))
+    }
+
+    // Uses the defaults when there is no config file in the default location.
+    pub fn load_default() -> Result<Config> {
+        match Config::get_default_path() {
+            Some(path) if path.exists() => Config::load(&path),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Config> {
+        let contents = read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("Could not read config file {}: {}", path.display(), e)))?;
+        Config::parse(&contents)
+            .map_err(|e| ErrorKind::other(format!("Invalid config file {}: {}", path.display(), e)))
+    }
+
+    pub fn parse(contents: &str) -> std::result::Result<Config, String> {
+        // An empty file, or one with only comments, is an empty document rather than an empty map.
+        let config: Config = match contents.lines().all(|l| l.trim().is_empty() || l.trim_start().starts_with('#')) {
+            true => Config::default(),
+            false => serde_yaml::from_str(contents).map_err(|e| e.to_string())?,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    // The built-in theme with the palette and colours set in the config file.
+    pub fn get_theme(&self, name: ThemeName) -> Theme {
+        let mut theme = name.get_theme();
+        if let Some(palette) = self.palette.get_palette() {
+            theme.palette = palette;
+        }
+        self.colors.apply(&mut theme);
+        theme
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        if !DIFFICULTIES.contains(&self.default_difficulty.to_lowercase().as_str()) {
+            return Err(format!("default_difficulty: unknown difficulty {}, expected easy, medium or hard", self.default_difficulty));
+        }
+        self.input.validate()?;
+        self.minesweeper.validate()?;
+        self.snake.validate()
+    }
+}
+
+// Every glyph has to fill the columns of a board cell exactly.
+pub(crate) fn validate_glyphs(section: &str, cell_width: i32, glyphs: &[(&str, char)]) -> std::result::Result<(), String> {
+    for (name, glyph) in glyphs {
+        match (glyph.width() == Some(cell_width as usize), cell_width) {
+            (true, _) => {}
+            (false, 2) => return Err(format!("{}.{}: `{}` is not a wide character, glyphs have to fill two columns", section, name, glyph)),
+            (false, _) => return Err(format!("{}.{}: `{}` is not a narrow character, glyphs have to fill one column", section, name, glyph)),
+        }
+    }
+    Ok(())
+}
+
+// A key can only do one thing in a game, and every action needs at least one key.
+pub(crate) fn validate_keys(section: &str, bindings: &[(&str, &Vec<KeyAction>)]) -> std::result::Result<(), String> {
+    for (i, (name, keys)) in bindings.iter().enumerate() {
+        if keys.is_empty() {
+            return Err(format!("{}.{}: needs at least one key", section, name));
+        }
+        for key in keys.iter() {
+            if let Some((other_name, _)) = bindings[i + 1..].iter().find(|(_, other_keys)| other_keys.contains(key)) {
+                return Err(format!("{}: {} is bound to both {} and {}", section, key, name, other_name));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+    use super::*;
+
+    #[test]
+    fn example_config_is_the_default() {
+        assert_eq!(Config::parse(include_str!("../../config.example.yaml")), Ok(Config::default()));
+    }
+
+    #[test]
+    fn empty_or_comment_only_file_is_the_default() {
+        assert_eq!(Config::parse(""), Ok(Config::default()));
+        assert_eq!(Config::parse("# theme: light\n\n  # poll_millis: 10\n"), Ok(Config::default()));
+    }
+
+    #[test]
+    fn given_keys_replace_only_their_default() {
+        let config = Config::parse("theme: light\nsnake:\n  min_step_millis: 50\n").unwrap();
+        assert_eq!(config.theme, ThemeName::Light);
+        assert_eq!(config.snake.min_step_millis, 50);
+        assert_eq!(config.snake.start_step_millis, SnakeConfig::default().start_step_millis);
+    }
+
+    #[test]
+    fn unknown_keys_are_errors() {
+        assert!(Config::parse("colour: red\n").unwrap_err().contains("unknown field `colour`"));
+        assert!(Config::parse("minesweeper:\n  keys:\n    jump: [x]\n").unwrap_err().contains("unknown field `jump`"));
+    }
+
+    #[test]
+    fn key_bound_to_two_actions_is_an_error() {
+        assert_eq!(
+            Config::parse("minesweeper:\n  keys:\n    flag: [f, d]\n"),
+            Err("minesweeper.keys: Char 'd' is bound to both dig and flag".to_string())
+        );
+        assert_eq!(Config::parse("snake:\n  keys:\n    up: []\n"), Err("snake.keys.up: needs at least one key".to_string()));
+        assert_eq!(Config::parse("input:\n  theme: [q]\n"), Err("input: Char 'q' is bound to both quit and theme".to_string()));
+    }
+
+    #[test]
+    fn glyphs_have_to_fill_the_cell() {
+        assert_eq!(
+            Config::parse("snake:\n  glyphs:\n    food: o\n"),
+            Err("snake.glyphs.food: `o` is not a wide character, glyphs have to fill two columns".to_string())
+        );
+        assert_eq!(
+            Config::parse("minesweeper:\n  glyph_set: ascii\n  glyphs:\n    flag: 🚩\n"),
+            Err("minesweeper.glyphs.flag: `🚩` is not a narrow character, glyphs have to fill one column".to_string())
+        );
+        let config = Config::parse("minesweeper:\n  glyph_set: ascii\n  glyphs:\n    flag: F\n").unwrap();
+        assert_eq!(config.minesweeper.glyphs.flag, Some('F'));
+    }
+
+    #[test]
+    fn number_colors_need_one_colour_for_each_number() {
+        assert_eq!(
+            Config::parse("minesweeper:\n  number_colors: [red, blue]\n"),
+            Err("minesweeper.number_colors: needs 8 colours, found 2".to_string())
+        );
+        let config = Config::parse("minesweeper:\n  number_colors: [red, red, red, red, red, red, red, blue]\n").unwrap();
+        assert_eq!(config.minesweeper.number_colors.unwrap()[7], Color::Blue);
+    }
+
+    #[test]
+    fn default_difficulty_is_checked_on_load() {
+        assert_eq!(
+            Config::parse("default_difficulty: nightmare\n"),
+            Err("default_difficulty: unknown difficulty nightmare, expected easy, medium or hard".to_string())
+        );
+        assert_eq!(Config::parse("default_difficulty: Large\n").unwrap().default_difficulty, "Large");
+    }
+
+    #[test]
+    fn values_out_of_range_are_errors() {
+        assert_eq!(Config::parse("input:\n  double_click_millis: 0\n"), Err("input.double_click_millis: must be 1-2000".to_string()));
+        assert_eq!(Config::parse("snake:\n  min_step_millis: 5\n"), Err("snake.min_step_millis: must be at least 10".to_string()));
+    }
+}
@@ -0,0 +1,29 @@
+use crossterm::style::Color;
+use serde::Deserialize;
+use tui::screen::theme::Theme;
+
+// Colours that replace the ones of the theme. None keeps the colour of the theme.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorConfig {
+    pub background: Option<Color>,
+    pub text: Option<Color>,
+    pub focused_border: Option<Color>,
+    // Background of the row picked with the keyboard in dialogs.
+    pub selected: Option<Color>,
+    pub error: Option<Color>,
+    pub win: Option<Color>,
+    pub lose: Option<Color>,
+}
+
+impl ColorConfig {
+    pub fn apply(&self, theme: &mut Theme) {
+        theme.window.bg = self.background.or(theme.window.bg);
+        theme.window.fg = self.text.or(theme.window.fg);
+        theme.focused_border.fg = self.focused_border.or(theme.focused_border.fg);
+        theme.selected.bg = self.selected.or(theme.selected.bg);
+        theme.error.fg = self.error.or(theme.error.fg);
+        theme.win.fg = self.win.or(theme.win.fg);
+        theme.lose.fg = self.lose.or(theme.lose.fg);
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use serde::Deserialize;
+
+// The characters a board is drawn with. Emoji need a terminal and font that draw them two columns
+// wide, the narrow sets work on Linux consoles and in tmux.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GlyphSet {
+    Emoji,
+    // Narrow Unicode symbols and box drawing characters.
+    Unicode,
+    Ascii,
+}
+
+impl GlyphSet {
+    // Columns each board cell takes.
+    pub fn get_cell_width(&self) -> i32 {
+        match self {
+            GlyphSet::Emoji => 2,
+            GlyphSet::Unicode | GlyphSet::Ascii => 1,
+        }
+    }
+
+    // The set after this one, for switching sets while playing.
+    pub fn next(&self) -> GlyphSet {
+        match self {
+            GlyphSet::Emoji => GlyphSet::Unicode,
+            GlyphSet::Unicode => GlyphSet::Ascii,
+            GlyphSet::Ascii => GlyphSet::Emoji,
+        }
+    }
+}
+
+impl Display for GlyphSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GlyphSet::Emoji => write!(f, "emoji"),
+            GlyphSet::Unicode => write!(f, "unicode"),
+            GlyphSet::Ascii => write!(f, "ascii"),
+        }
+    }
+}
+
+impl FromStr for GlyphSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "emoji" => Ok(GlyphSet::Emoji),
+            "unicode" => Ok(GlyphSet::Unicode),
+            "ascii" => Ok(GlyphSet::Ascii),
+            _ => Err(format!("Unknown glyph set {}, expected emoji, unicode or ascii", s)),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use serde::Deserialize;
+use tui::screen::window::key_action::KeyAction;
+
+const MAX_DOUBLE_CLICK_MILLIS: u64 = 2000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InputConfig {
+    // Two left clicks on the same cell within this time are a double click.
+    pub double_click_millis: u64,
+    // Longest wait for input before the screen is drawn again. 0 waits until the next tick or input.
+    pub poll_millis: u64,
+    // Keys that quit when the focused window does not use them.
+    pub quit: Vec<KeyAction>,
+    // Switches to the next theme when the focused window does not use the key.
+    pub theme: Vec<KeyAction>,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        InputConfig {
+            double_click_millis: 500,
+            poll_millis: 0,
+            quit: vec![KeyAction::Char('q')],
+            theme: vec![KeyAction::Char('t')],
+        }
+    }
+}
+
+impl InputConfig {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_DOUBLE_CLICK_MILLIS).contains(&self.double_click_millis) {
+            return Err(format!("input.double_click_millis: must be 1-{}", MAX_DOUBLE_CLICK_MILLIS));
+        }
+        // Either list can be empty, to leave the action without a key.
+        if let Some(key) = self.theme.iter().find(|key| self.quit.contains(key)) {
+            return Err(format!("input: {} is bound to both quit and theme", key));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use crossterm::style::Color;
+use serde::Deserialize;
+use crate::config::glyph_set::GlyphSet;
+use crate::config::minesweeper_glyphs::MinesweeperGlyphs;
+use crate::config::minesweeper_keys::MinesweeperKeys;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MinesweeperConfig {
+    // Background of the cell picked with the keyboard. None uses the selection of the theme.
+    pub cursor_color: Option<Color>,
+    // Colours of the numbers 1 to 8. None uses the palette of the theme.
+    pub number_colors: Option<Vec<Color>>,
+    pub glyph_set: GlyphSet,
+    pub glyphs: MinesweeperGlyphs,
+    pub keys: MinesweeperKeys,
+    // Only deals boards that can be solved from the first click without guessing. The custom
+    // game dialog starts with this ticked.
+    pub no_guess: bool,
+}
+
+impl Default for MinesweeperConfig {
+    fn default() -> Self {
+        MinesweeperConfig {
+            cursor_color: None,
+            number_colors: None,
+            glyph_set: GlyphSet::Emoji,
+            glyphs: MinesweeperGlyphs::default(),
+            keys: MinesweeperKeys::default(),
+            no_guess: false,
+        }
+    }
+}
+
+impl MinesweeperConfig {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if let Some(number_colors) = &self.number_colors {
+            if number_colors.len() != 8 {
+                return Err(format!("minesweeper.number_colors: needs 8 colours, found {}", number_colors.len()));
+            }
+        }
+        self.glyphs.validate(self.glyph_set)?;
+        self.keys.validate()
+    }
+}
@@ -0,0 +1,39 @@
+use serde::Deserialize;
+use unicode_width::UnicodeWidthChar;
+use crate::config::glyph_set::GlyphSet;
+use crate::config::validate_glyphs;
+
+// Glyphs that replace those of the glyph set. Glyphs that do not fit the cells of the set in use,
+// after switching sets while playing, are left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MinesweeperGlyphs {
+    pub unchecked: Option<char>,
+    // A dug cell with no mines next to it.
+    pub empty: Option<char>,
+    pub flag: Option<char>,
+    pub mine: Option<char>,
+    // A flag on a cell without a mine, shown when the game is lost.
+    pub wrong_flag: Option<char>,
+    pub exploded: Option<char>,
+}
+
+impl MinesweeperGlyphs {
+    // The glyph, when it fits the cells of the glyph set.
+    pub fn get(glyph: Option<char>, glyph_set: GlyphSet) -> Option<char> {
+        glyph.filter(|glyph| glyph.width() == Some(glyph_set.get_cell_width() as usize))
+    }
+
+    pub(crate) fn validate(&self, glyph_set: GlyphSet) -> Result<(), String> {
+        let glyphs = [
+            ("unchecked", self.unchecked),
+            ("empty", self.empty),
+            ("flag", self.flag),
+            ("mine", self.mine),
+            ("wrong_flag", self.wrong_flag),
+            ("exploded", self.exploded),
+        ];
+        let given: Vec<(&str, char)> = glyphs.iter().filter_map(|(name, glyph)| glyph.map(|g| (*name, g))).collect();
+        validate_glyphs("minesweeper.glyphs", glyph_set.get_cell_width(), &given)
+    }
+}
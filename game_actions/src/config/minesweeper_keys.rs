@@ -0,0 +1,68 @@
+use serde::Deserialize;
+use tui::screen::window::key_action::KeyAction;
+use tui::screen::window::key_action::KeyAction::Char;
+use crate::config::validate_keys;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MinesweeperKeys {
+    pub up: Vec<KeyAction>,
+    pub down: Vec<KeyAction>,
+    pub left: Vec<KeyAction>,
+    pub right: Vec<KeyAction>,
+    pub dig: Vec<KeyAction>,
+    pub flag: Vec<KeyAction>,
+    pub dig_around: Vec<KeyAction>,
+    pub hint: Vec<KeyAction>,
+    pub retry: Vec<KeyAction>,
+    // Takes back the last move, or plays it again. Games that used undo get no best time.
+    pub undo: Vec<KeyAction>,
+    pub redo: Vec<KeyAction>,
+    // Play a replay faster or slower.
+    pub faster: Vec<KeyAction>,
+    pub slower: Vec<KeyAction>,
+    // Switches to the next glyph set.
+    pub glyph_set: Vec<KeyAction>,
+}
+
+impl Default for MinesweeperKeys {
+    fn default() -> Self {
+        MinesweeperKeys {
+            up: vec![KeyAction::Up, Char('k')],
+            down: vec![KeyAction::Down, Char('j')],
+            left: vec![KeyAction::Left, Char('h')],
+            right: vec![KeyAction::Right, Char('l')],
+            dig: vec![Char(' '), Char('d')],
+            flag: vec![Char('f')],
+            dig_around: vec![KeyAction::Enter, Char('a')],
+            hint: vec![Char('?')],
+            retry: vec![Char('r')],
+            undo: vec![Char('u')],
+            redo: vec![Char('y')],
+            faster: vec![Char('+'), Char('=')],
+            slower: vec![Char('-')],
+            glyph_set: vec![Char('g')],
+        }
+    }
+}
+
+impl MinesweeperKeys {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        validate_keys("minesweeper.keys", &[
+            ("up", &self.up),
+            ("down", &self.down),
+            ("left", &self.left),
+            ("right", &self.right),
+            ("dig", &self.dig),
+            ("flag", &self.flag),
+            ("dig_around", &self.dig_around),
+            ("hint", &self.hint),
+            ("retry", &self.retry),
+            ("undo", &self.undo),
+            ("redo", &self.redo),
+            ("faster", &self.faster),
+            ("slower", &self.slower),
+            ("glyph_set", &self.glyph_set),
+        ])
+    }
+}
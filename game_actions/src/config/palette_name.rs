@@ -0,0 +1,138 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crossterm::style::{Attribute, Color};
+use serde::Deserialize;
+use tui::screen::style::Style;
+
+// Colours for telling counts apart, such as the minesweeper numbers 1 to 8. Theme keeps the
+// palette of the theme, the others are for colour blindness. They do not lean on colour alone:
+// even numbers are bold and 5 to 8 are underlined, and monochrome uses attributes only.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaletteName {
+    Theme,
+    // Red and green are hard to tell apart, so blues, oranges and purples are used.
+    Deuteranopia,
+    // Like deuteranopia, but reds also look dark, so there are none.
+    Protanopia,
+    // Blue and yellow are hard to tell apart, so reds, teals and pinks are used.
+    Tritanopia,
+    Monochrome,
+}
+
+const ALL: [PaletteName; 5] = [
+    PaletteName::Theme,
+    PaletteName::Deuteranopia,
+    PaletteName::Protanopia,
+    PaletteName::Tritanopia,
+    PaletteName::Monochrome,
+];
+
+impl PaletteName {
+    // None when the palette of the theme is kept.
+    pub fn get_palette(&self) -> Option<[Style; 8]> {
+        let colors = match self {
+            PaletteName::Theme => return None,
+            PaletteName::Deuteranopia => [
+                (0, 114, 178),
+                (230, 159, 0),
+                (86, 180, 233),
+                (213, 94, 0),
+                (204, 121, 167),
+                (0, 158, 115),
+                (128, 128, 128),
+                (153, 79, 0),
+            ],
+            PaletteName::Protanopia => [
+                (0, 114, 178),
+                (230, 159, 0),
+                (86, 180, 233),
+                (170, 120, 0),
+                (120, 94, 240),
+                (204, 121, 167),
+                (128, 128, 128),
+                (0, 73, 112),
+            ],
+            PaletteName::Tritanopia => [
+                (0, 158, 158),
+                (220, 50, 32),
+                (0, 110, 80),
+                (230, 97, 160),
+                (128, 128, 128),
+                (150, 30, 30),
+                (0, 90, 110),
+                (200, 0, 120),
+            ],
+            PaletteName::Monochrome => return Some(get_monochrome_palette()),
+        };
+        let mut palette = [Style::default(); 8];
+        for (i, (r, g, b)) in colors.into_iter().enumerate() {
+            palette[i] = Style::new_with_attributes(Some(Color::Rgb { r, g, b }), None, &get_cues(i + 1));
+        }
+        Some(palette)
+    }
+
+    // The palette after this one, for picking palettes in the settings window.
+    pub fn next(&self) -> PaletteName {
+        ALL[(self.get_index() + 1) % ALL.len()]
+    }
+
+    pub fn previous(&self) -> PaletteName {
+        ALL[(self.get_index() + ALL.len() - 1) % ALL.len()]
+    }
+
+    fn get_index(&self) -> usize {
+        ALL.iter().position(|p| p == self).unwrap_or(0)
+    }
+}
+
+fn get_cues(number: usize) -> Vec<Attribute> {
+    let mut cues = vec![];
+    if number.is_multiple_of(2) {
+        cues.push(Attribute::Bold);
+    }
+    if number > 4 {
+        cues.push(Attribute::Underlined);
+    }
+    cues
+}
+
+// The text colour of the theme, with every mix of bold, underline and reverse video.
+fn get_monochrome_palette() -> [Style; 8] {
+    let mut palette = [Style::default(); 8];
+    for (i, style) in palette.iter_mut().enumerate() {
+        let mut attributes = get_cues(i + 1);
+        if i % 4 >= 2 {
+            attributes.push(Attribute::Reverse);
+        }
+        *style = Style::new_with_attributes(None, None, &attributes);
+    }
+    palette
+}
+
+impl Display for PaletteName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaletteName::Theme => write!(f, "theme"),
+            PaletteName::Deuteranopia => write!(f, "deuteranopia"),
+            PaletteName::Protanopia => write!(f, "protanopia"),
+            PaletteName::Tritanopia => write!(f, "tritanopia"),
+            PaletteName::Monochrome => write!(f, "monochrome"),
+        }
+    }
+}
+
+impl FromStr for PaletteName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "theme" => Ok(PaletteName::Theme),
+            "deuteranopia" => Ok(PaletteName::Deuteranopia),
+            "protanopia" => Ok(PaletteName::Protanopia),
+            "tritanopia" => Ok(PaletteName::Tritanopia),
+            "monochrome" => Ok(PaletteName::Monochrome),
+            _ => Err(format!("Unknown palette {}, expected theme, deuteranopia, protanopia, tritanopia or monochrome", s)),
+        }
+    }
+}
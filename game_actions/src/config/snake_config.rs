@@ -0,0 +1,42 @@
+use serde::Deserialize;
+use crate::config::snake_glyphs::SnakeGlyphs;
+use crate::config::snake_keys::SnakeKeys;
+
+const MIN_STEP_MILLIS: u64 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnakeConfig {
+    // The snake starts at one step every start_step_millis and gets step_millis_per_food
+    // faster for each food eaten, until it reaches min_step_millis.
+    pub start_step_millis: u64,
+    pub min_step_millis: u64,
+    pub step_millis_per_food: u64,
+    pub glyphs: SnakeGlyphs,
+    pub keys: SnakeKeys,
+}
+
+impl Default for SnakeConfig {
+    fn default() -> Self {
+        SnakeConfig {
+            start_step_millis: 200,
+            min_step_millis: 70,
+            step_millis_per_food: 5,
+            glyphs: SnakeGlyphs::default(),
+            keys: SnakeKeys::default(),
+        }
+    }
+}
+
+impl SnakeConfig {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.min_step_millis < MIN_STEP_MILLIS {
+            return Err(format!("snake.min_step_millis: must be at least {}", MIN_STEP_MILLIS));
+        }
+        if self.start_step_millis < self.min_step_millis {
+            return Err("snake.start_step_millis: must be at least snake.min_step_millis".to_string());
+        }
+        self.glyphs.validate()?;
+        self.keys.validate()
+    }
+}
@@ -0,0 +1,40 @@
+use serde::Deserialize;
+use crate::config::validate_glyphs;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnakeGlyphs {
+    pub empty: char,
+    pub head: char,
+    pub body: char,
+    pub food: char,
+    // The icons of the length and the time in the header, next to the food for the score.
+    pub length: char,
+    pub clock: char,
+}
+
+impl Default for SnakeGlyphs {
+    fn default() -> Self {
+        SnakeGlyphs {
+            empty: '⬛',
+            head: '🟢',
+            body: '🟩',
+            food: '🍎',
+            length: '🐍',
+            clock: '🕑',
+        }
+    }
+}
+
+impl SnakeGlyphs {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        validate_glyphs("snake.glyphs", 2, &[
+            ("empty", self.empty),
+            ("head", self.head),
+            ("body", self.body),
+            ("food", self.food),
+            ("length", self.length),
+            ("clock", self.clock),
+        ])
+    }
+}
@@ -0,0 +1,38 @@
+use serde::Deserialize;
+use tui::screen::window::key_action::KeyAction;
+use tui::screen::window::key_action::KeyAction::Char;
+use crate::config::validate_keys;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnakeKeys {
+    pub up: Vec<KeyAction>,
+    pub down: Vec<KeyAction>,
+    pub left: Vec<KeyAction>,
+    pub right: Vec<KeyAction>,
+    pub retry: Vec<KeyAction>,
+}
+
+impl Default for SnakeKeys {
+    fn default() -> Self {
+        SnakeKeys {
+            up: vec![KeyAction::Up, Char('w')],
+            down: vec![KeyAction::Down, Char('s')],
+            left: vec![KeyAction::Left, Char('a')],
+            right: vec![KeyAction::Right, Char('d')],
+            retry: vec![Char('r')],
+        }
+    }
+}
+
+impl SnakeKeys {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        validate_keys("snake.keys", &[
+            ("up", &self.up),
+            ("down", &self.down),
+            ("left", &self.left),
+            ("right", &self.right),
+            ("retry", &self.retry),
+        ])
+    }
+}
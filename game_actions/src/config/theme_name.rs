@@ -0,0 +1,64 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use serde::Deserialize;
+use tui::screen::theme::Theme;
+
+// The built-in themes. Dark keeps the colours of the terminal, light and high contrast draw
+// windows on a background of their own.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl ThemeName {
+    pub fn get_theme(&self) -> Theme {
+        match self {
+            ThemeName::Dark => Theme::dark(),
+            ThemeName::Light => Theme::light(),
+            ThemeName::HighContrast => Theme::high_contrast(),
+        }
+    }
+
+    // The theme after this one, for switching themes while playing.
+    pub fn next(&self) -> ThemeName {
+        match self {
+            ThemeName::Dark => ThemeName::Light,
+            ThemeName::Light => ThemeName::HighContrast,
+            ThemeName::HighContrast => ThemeName::Dark,
+        }
+    }
+
+    pub fn previous(&self) -> ThemeName {
+        match self {
+            ThemeName::Dark => ThemeName::HighContrast,
+            ThemeName::Light => ThemeName::Dark,
+            ThemeName::HighContrast => ThemeName::Light,
+        }
+    }
+}
+
+impl Display for ThemeName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeName::Dark => write!(f, "dark"),
+            ThemeName::Light => write!(f, "light"),
+            ThemeName::HighContrast => write!(f, "high-contrast"),
+        }
+    }
+}
+
+impl FromStr for ThemeName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dark" => Ok(ThemeName::Dark),
+            "light" => Ok(ThemeName::Light),
+            "high-contrast" => Ok(ThemeName::HighContrast),
+            _ => Err(format!("Unknown theme {}, expected dark, light or high-contrast", s)),
+        }
+    }
+}
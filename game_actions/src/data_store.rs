@@ -0,0 +1,187 @@
+use std::env;
+use std::fs::{copy, create_dir_all, remove_file, rename, File};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const DATA_DIR_NAME: &str = "terminal_games";
+
+// Reads and writes the YAML files a game keeps between runs, such as scores and replays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataStore {
+    dir: PathBuf,
+}
+
+impl DataStore {
+    pub fn new(dir: PathBuf) -> Self {
+        DataStore { dir }
+    }
+
+    // $XDG_DATA_HOME/terminal_games, or ~/.local/share/terminal_games.
+    pub fn get_default_dir() -> Option<PathBuf> {
+        let data_home = match env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?).join(".local").join("share"),
+        };
+        Some(data_home.join(DATA_DIR_NAME))
+    }
+
+    // Where older versions kept their files.
+    pub fn get_exe_dir() -> Option<PathBuf> {
+        let mut path = env::current_exe().ok()?;
+        path.pop();
+        Some(path)
+    }
+
+    pub fn get_dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn get_path(&self, file_name: &str) -> PathBuf {
+        self.dir.join(file_name)
+    }
+
+    // None when the file does not exist yet.
+    pub fn load<T: DeserializeOwned>(&self, file_name: &str) -> crossterm::Result<Option<T>> {
+        let path = self.get_path(file_name);
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(DataStore::describe_error("read", &path, e)),
+        };
+        serde_yaml::from_reader(file)
+            .map(Some)
+            .map_err(|e| DataStore::describe_error("read", &path, io::Error::new(ErrorKind::InvalidData, e)))
+    }
+
+    // Writes to a temporary file first and renames it over the old one, so a crash or a full
+    // disk never leaves a half written file behind.
+    pub fn save<T: Serialize>(&self, file_name: &str, value: &T) -> crossterm::Result<()> {
+        let path = self.get_path(file_name);
+        let temp_path = self.get_path(&format!(".{}.tmp", file_name));
+        let result = serde_yaml::to_string(value)
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+            .and_then(|yaml| {
+                create_dir_all(&self.dir)?;
+                let mut file = File::create(&temp_path)?;
+                file.write_all(yaml.as_bytes())?;
+                file.sync_all()?;
+                rename(&temp_path, &path)
+            });
+        if result.is_err() {
+            let _ = remove_file(&temp_path);
+        }
+        result.map_err(|e| DataStore::describe_error("save", &path, e))
+    }
+
+    // Does nothing when the file is already gone.
+    pub fn remove(&self, file_name: &str) -> crossterm::Result<()> {
+        let path = self.get_path(file_name);
+        match remove_file(&path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(DataStore::describe_error("remove", &path, e)),
+            _ => Ok(()),
+        }
+    }
+
+    // Moves a file from old_dir into the store, unless the store already has one.
+    // Returns whether anything was moved.
+    pub fn migrate(&self, old_dir: &Path, file_name: &str) -> crossterm::Result<bool> {
+        let old_path = old_dir.join(file_name);
+        let path = self.get_path(file_name);
+        if old_dir == self.dir || !old_path.is_file() || path.exists() {
+            return Ok(false);
+        }
+        let result = create_dir_all(&self.dir).and_then(|_| copy(&old_path, &path));
+        if let Err(e) = result {
+            return Err(DataStore::describe_error("migrate", &old_path, e));
+        }
+        // The old directory is often read only, in which case the old file just stays behind.
+        let _ = remove_file(&old_path);
+        Ok(true)
+    }
+
+    fn describe_error(action: &str, path: &Path, error: io::Error) -> io::Error {
+        io::Error::new(error.kind(), format!("Could not {} {}: {}", action, path.display(), error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::fs::{read_dir, read_to_string, write};
+    use tempfile::TempDir;
+
+    // Saving into a directory that does not exist yet has to create it.
+    fn data_store(temp_dir: &TempDir) -> DataStore {
+        DataStore::new(temp_dir.path().join("data"))
+    }
+
+    #[test]
+    fn saved_file_loads_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_store = data_store(&temp_dir);
+        let scores = HashMap::from([("easy".to_string(), 12), ("hard".to_string(), 300)]);
+        data_store.save("scores.yaml", &scores).unwrap();
+        assert_eq!(data_store.load::<HashMap<String, u64>>("scores.yaml").unwrap(), Some(scores));
+    }
+
+    #[test]
+    fn save_replaces_the_file_and_leaves_no_temporary_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_store = data_store(&temp_dir);
+        data_store.save("value.yaml", &1).unwrap();
+        data_store.save("value.yaml", &2).unwrap();
+        assert_eq!(data_store.load::<u32>("value.yaml").unwrap(), Some(2));
+        let file_names: Vec<_> = read_dir(data_store.get_dir()).unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(file_names, vec!["value.yaml"]);
+    }
+
+    #[test]
+    fn missing_file_loads_as_none() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(data_store(&temp_dir).load::<u32>("missing.yaml").unwrap(), None);
+    }
+
+    #[test]
+    fn broken_file_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_store = data_store(&temp_dir);
+        data_store.save("value.yaml", &"text").unwrap();
+        assert!(data_store.load::<u32>("value.yaml").is_err());
+    }
+
+    #[test]
+    fn migrate_moves_the_old_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_store = data_store(&temp_dir);
+        write(temp_dir.path().join("value.yaml"), "1\n").unwrap();
+        assert!(data_store.migrate(temp_dir.path(), "value.yaml").unwrap());
+        assert_eq!(data_store.load::<u32>("value.yaml").unwrap(), Some(1));
+        assert!(!temp_dir.path().join("value.yaml").exists());
+    }
+
+    #[test]
+    fn migrate_never_overwrites_a_file_in_the_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_store = data_store(&temp_dir);
+        data_store.save("value.yaml", &2).unwrap();
+        write(temp_dir.path().join("value.yaml"), "1\n").unwrap();
+        assert!(!data_store.migrate(temp_dir.path(), "value.yaml").unwrap());
+        assert_eq!(data_store.load::<u32>("value.yaml").unwrap(), Some(2));
+        assert_eq!(read_to_string(temp_dir.path().join("value.yaml")).unwrap(), "1\n");
+    }
+
+    #[test]
+    fn remove_of_a_missing_file_is_fine() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_store = data_store(&temp_dir);
+        data_store.save("value.yaml", &1).unwrap();
+        data_store.remove("value.yaml").unwrap();
+        data_store.remove("value.yaml").unwrap();
+        assert_eq!(data_store.load::<u32>("value.yaml").unwrap(), None);
+    }
+}
@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+use crate::config::Config;
+use crate::data_store::DataStore;
+
+// Settings from the command line and config file that every game is created with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameContext {
+    // Boards are generated from this seed when set, so a game can be played again.
+    pub seed: Option<u64>,
+    // Where scores and replays are kept, when not the default location.
+    pub data_dir: Option<PathBuf>,
+    pub config: Config,
+}
+
+impl GameContext {
+    // Defaults to $XDG_DATA_HOME/terminal_games, or the directory of the executable when
+    // there is no home directory.
+    pub fn get_data_dir(&self) -> PathBuf {
+        self.data_dir.clone()
+            .or_else(DataStore::get_default_dir)
+            .or_else(DataStore::get_exe_dir)
+            .unwrap_or_default()
+    }
+
+    pub fn get_data_store(&self) -> DataStore {
+        DataStore::new(self.get_data_dir())
+    }
+}
@@ -0,0 +1,32 @@
+use std::path::Path;
+use tui::screen::window::component::Component;
+use crate::click_action::ClickAction;
+use crate::game_context::GameContext;
+use crate::game_variant::GameVariant;
+use crate::start_options::StartOptions;
+
+// Everything the launcher needs to show a game in the main menu and open its windows.
+pub trait GameDescriptor {
+    // Identifies the game in ClickAction::Open.
+    fn get_id(&self) -> &'static str;
+    fn get_name(&self) -> &'static str;
+    // The variants shown in the main menu, in order.
+    fn get_variants(&self) -> Vec<GameVariant>;
+    fn get_variant_label(&self, variant: &GameVariant) -> String;
+    fn get_window_title(&self, variant: &GameVariant) -> String;
+    // The variant to open for the difficulty or board size given on the command line.
+    fn get_start_variant(&self, options: &StartOptions, context: &GameContext) -> Result<GameVariant, String>;
+    // Files the game keeps in the data directory, moved there from where older versions kept them.
+    fn get_data_files(&self) -> Vec<&'static str> {
+        vec![]
+    }
+    // Writes the leaderboard to a file so it can be shared, returning how many entries were written.
+    fn export_leaderboard(&self, _context: &GameContext, _path: &Path) -> crossterm::Result<usize> {
+        Err(crossterm::ErrorKind::other(format!("{} has no leaderboard", self.get_name())))
+    }
+    // Merges a shared leaderboard into the local one, returning how many entries were added.
+    fn import_leaderboard(&self, _context: &GameContext, _path: &Path) -> crossterm::Result<usize> {
+        Err(crossterm::ErrorKind::other(format!("{} has no leaderboard", self.get_name())))
+    }
+    fn create(&self, variant: &GameVariant, context: &GameContext) -> crossterm::Result<Box<dyn Component<ClickAction>>>;
+}
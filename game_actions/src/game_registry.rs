@@ -0,0 +1,25 @@
+use crate::game_descriptor::GameDescriptor;
+
+// The games the launcher knows about, in menu order.
+#[derive(Default)]
+pub struct GameRegistry {
+    games: Vec<Box<dyn GameDescriptor>>,
+}
+
+impl GameRegistry {
+    pub fn new() -> Self {
+        GameRegistry { games: vec![] }
+    }
+
+    pub fn register(&mut self, game: Box<dyn GameDescriptor>) {
+        self.games.push(game);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn GameDescriptor> {
+        self.games.iter().find(|g| g.get_id() == id).map(|g| g.as_ref())
+    }
+
+    pub fn get_games(&self) -> &[Box<dyn GameDescriptor>] {
+        &self.games
+    }
+}
@@ -0,0 +1,16 @@
+// Something a game can open from the main menu or the command line. Only the GameDescriptor of
+// the game knows what the id stands for, such as a difficulty or the settings of a custom board.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameVariant {
+    id: String,
+}
+
+impl GameVariant {
+    pub fn new(id: impl Into<String>) -> Self {
+        GameVariant { id: id.into() }
+    }
+
+    pub fn get_id(&self) -> &str {
+        &self.id
+    }
+}
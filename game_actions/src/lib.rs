@@ -1,2 +1,8 @@
 pub mod click_action;
-pub mod game_type;
+pub mod config;
+pub mod data_store;
+pub mod game_context;
+pub mod game_descriptor;
+pub mod game_registry;
+pub mod game_variant;
+pub mod start_options;
@@ -0,0 +1,10 @@
+// The game asked for on the command line, which the GameDescriptor turns into a GameVariant.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StartOptions {
+    // Such as easy or large. The default difficulty of the config is used when neither this
+    // nor a board size is given.
+    pub difficulty: Option<String>,
+    // Width and height of a custom board.
+    pub size: Option<(i32, i32)>,
+    pub mines: Option<i32>,
+}
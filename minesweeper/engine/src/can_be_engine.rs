@@ -3,7 +3,9 @@ use crate::cell::Cell;
 use crate::cell_state::CellState;
 use crate::game_state::GameState;
 use crate::game_stats::GameStats;
+use crate::hint::Hint;
 use crate::move_type::MoveType;
+use crate::replay::Replay;
 use crate::zero_to_eight::ZeroToEight;
 
 pub trait CanBeEngine {
@@ -14,4 +16,14 @@ pub trait CanBeEngine {
     fn play_move(&mut self, move_type: MoveType, cell: Cell) -> crossterm::Result<GameState>;
     fn make_clone(&self) -> Box<dyn CanBeEngine>;
     fn get_chill_factor(&mut self, cell: Cell) -> crossterm::Result<ZeroToEight>;
+    fn get_hint(&mut self) -> crossterm::Result<Hint>;
+    // None until the board has been generated, and for engines that are not playing a live game.
+    fn get_replay(&self) -> Option<Replay>;
+    fn undo(&mut self) -> crossterm::Result<GameState>;
+    fn redo(&mut self) -> crossterm::Result<GameState>;
+    // How many times faster than real time a replay plays. None for engines of live games.
+    fn get_speed(&self) -> Option<f64> {
+        None
+    }
+    fn set_speed(&mut self, _speed: f64) {}
 }
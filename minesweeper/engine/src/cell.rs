@@ -1,4 +1,6 @@
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Cell {
     pub x: i32,
     pub y: i32
@@ -7,6 +9,6 @@ pub struct Cell {
 impl From<Cell> for (i32, i32) {
     fn from(c: Cell) -> (i32, i32) {
         let Cell {x, y} = c;
-        return (x, y);
+        (x, y)
     }
 }
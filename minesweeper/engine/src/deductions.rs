@@ -0,0 +1,8 @@
+use std::collections::HashSet;
+use crate::Cell;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Deductions {
+    pub safe_cells: HashSet<Cell>,
+    pub mine_cells: HashSet<Cell>,
+}
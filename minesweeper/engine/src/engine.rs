@@ -1,9 +1,18 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 use crossterm::{ErrorKind};
-use std::io;
-use rand::Rng;
+use rand::{Rng, RngCore, SeedableRng};
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
 use queues::{IsQueue, queue, Queue};
+use log::info;
+use crate::generation_mode::{GenerationMode, NoGuessFallback};
+use crate::hint::Hint;
+use crate::logged_move::LoggedMove;
+use crate::replay::Replay;
+use crate::replay_action::ReplayAction;
+use crate::move_history::{CellChange, EngineSnapshot, HistoryEntry, MoveHistory};
+use crate::solver::Solver;
 use crate::{Bomb, CanBeEngine, Cell, CellState, Checked, Complete, Cross, Dig, DigAround, Exploded, Flag, Flagged, GameState, GameStats, Lose, MoveType, Playing, Unchecked, Win, Zero, ZeroToEight};
 
 #[derive(Debug, Clone)]
@@ -19,12 +28,31 @@ pub struct Engine {
     flagged_cells: i32,
     total_cells: i32,
     start_instant: Option<Instant>,
-    game_complete_time: u64,
-    changed_cells: HashSet<Cell>
+    game_complete_millis: u64,
+    changed_cells: HashSet<Cell>,
+    seed: u64,
+    generation_mode: GenerationMode,
+    history: MoveHistory,
+    // Collects the cells changed by the move being played, while one is being played.
+    pending_cell_changes: Option<Vec<CellChange>>,
+    used_undo: bool,
+    // Set when a no-guess board was asked for but none was found in time.
+    no_guess_fallback: bool,
+    move_log: Vec<LoggedMove>
 }
 
 impl Engine {
     pub fn new(width: i32, height: i32, bomb_count: i32) -> Self {
+        Engine::new_with_seed(width, height, bomb_count, rand::thread_rng().gen())
+    }
+
+    // The seed is drawn from the given rng, so it can still be read back with get_seed.
+    pub fn new_with_rng<R: RngCore>(width: i32, height: i32, bomb_count: i32, rng: &mut R) -> Self {
+        Engine::new_with_seed(width, height, bomb_count, rng.next_u64())
+    }
+
+    // The same seed and the same first click will always produce the same mine layout.
+    pub fn new_with_seed(width: i32, height: i32, bomb_count: i32, seed: u64) -> Self {
         let total_cells = width * height;
         let mut board_play_state = HashMap::new();
         let mut board_state = HashMap::new();
@@ -49,26 +77,72 @@ impl Engine {
             total_cells,
             board_initialised: false,
             start_instant: None,
-            game_complete_time: 0,
-            changed_cells
+            game_complete_millis: 0,
+            changed_cells,
+            seed,
+            generation_mode: GenerationMode::default(),
+            history: MoveHistory::default(),
+            pending_cell_changes: None,
+            used_undo: false,
+            no_guess_fallback: false,
+            move_log: vec![]
+        }
+    }
+
+    // Starts from a fixed mine layout instead of generating one on the first move.
+    pub fn new_with_layout(width: i32, height: i32, mines: &[Cell], seed: u64) -> crossterm::Result<Self> {
+        let mut engine = Engine::new_with_seed(width, height, mines.len() as i32, seed);
+        for cell in mines {
+            if cell.x < 0 || cell.y < 0 || cell.x >= width || cell.y >= height || engine.board_state[cell] == Bomb {
+                Err(ErrorKind::other("Mine layout has a mine out of range or placed twice"))?;
+            }
+            engine.board_state.insert(*cell, Bomb);
+            engine.increment_bomb_count_of_surrounding_cells(*cell)?;
+        }
+        engine.board_initialised = true;
+        Ok(engine)
+    }
+
+    // A board needs room for every mine outside the first move and its neighbours.
+    pub fn validate_settings(width: i32, height: i32, bomb_count: i32) -> crossterm::Result<()> {
+        if width < 1 || height < 1 {
+            Err(ErrorKind::other("The board must be at least 1 cell wide and 1 cell high"))?;
         }
+        let max_bomb_count = width * height - 9;
+        if bomb_count < 1 || bomb_count > max_bomb_count {
+            Err(ErrorKind::other(format!("Mines must be 1-{}", max_bomb_count.max(1))))?;
+        }
+        Ok(())
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    // Only has an effect before the first move, as that is when the board is generated.
+    pub fn set_generation_mode(&mut self, generation_mode: GenerationMode) {
+        self.generation_mode = generation_mode;
+    }
+
+    pub fn get_generation_mode(&self) -> GenerationMode {
+        self.generation_mode
     }
 
     pub fn is_game_won(&self) -> bool {
-        return self.checked_cells + self.flagged_cells == self.total_cells &&
-            self.flagged_cells == self.bomb_count;
+        self.checked_cells + self.flagged_cells == self.total_cells &&
+            self.flagged_cells == self.bomb_count
     }
 
     pub fn win_game(&mut self) {
         if let Some(start_instant) = self.start_instant{
-            self.game_complete_time = start_instant.elapsed().as_secs();
+            self.game_complete_millis = start_instant.elapsed().as_millis() as u64;
         }
         self.game_state = Complete(Win);
     }
 
     pub fn lose_game(&mut self) {
         if let Some(start_instant) = self.start_instant{
-            self.game_complete_time = start_instant.elapsed().as_secs();
+            self.game_complete_millis = start_instant.elapsed().as_millis() as u64;
         }
         self.game_state = Complete(Lose);
         for x in 0..self.width {
@@ -111,34 +185,49 @@ impl Engine {
         let mut safe_cells = self.get_surrounding_cells(clicked_cell, None);
         safe_cells.push(clicked_cell);
 
-        let mut rng = rand::thread_rng();
-        let bomb_probability = self.bomb_count as f64 / self.total_cells as f64;
-        let mut get_bomb_or_not = || rng.gen_range(0.0..1.0) <= bomb_probability;
-        let mut local_bomb_count = self.bomb_count;
-        while local_bomb_count > 0 {
-            for x in 0..self.width {
-                for y in 0..self.height {
-                    let cell = Cell {x, y};
-                    // Ensure no bomb is placed on or around the clicked cell!
-                    if  safe_cells.contains(&cell) {
-                        continue;
-                    }
-
-                    if self.board_state[&cell] == Bomb {
-                        continue;
-                    }
+        let mut candidate_cells = vec![];
+        for x in 0..self.width {
+            for y in 0..self.height {
+                let cell = Cell {x, y};
+                // Ensure no bomb is placed on or around the clicked cell!
+                if !safe_cells.contains(&cell) {
+                    candidate_cells.push(cell);
+                }
+            }
+        }
+        if candidate_cells.len() < self.bomb_count as usize {
+            Err(ErrorKind::other("Too many bombs! There is not enough room around the first move"))?;
+        }
 
-                    if get_bomb_or_not() {
-                        self.board_state.insert(cell, Bomb);
-                        local_bomb_count -= 1;
-                        self.increment_bomb_count_of_surrounding_cells(cell)?;
-                    }
-                    if local_bomb_count == 0 {
+        // Only the seed decides the layout, so a board can be recreated from its seed and first click.
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        match self.generation_mode {
+            GenerationMode::Random => self.place_bombs(&mut candidate_cells, &mut rng)?,
+            GenerationMode::NoGuess(settings) => {
+                let search_start = Instant::now();
+                let mut attempts = 0;
+                let mut solvable = false;
+                // At least one layout is placed, however little time is allowed.
+                while attempts < settings.max_attempts.max(1) && (attempts == 0 || search_start.elapsed() < settings.max_time) {
+                    attempts += 1;
+                    self.place_bombs(&mut candidate_cells, &mut rng)?;
+                    if self.is_solvable_from(clicked_cell) {
+                        info!("Found a no-guess board after {} attempt(s)", attempts);
+                        solvable = true;
                         break;
                     }
                 }
-                if local_bomb_count == 0 {
-                    break;
+                self.no_guess_fallback = !solvable;
+                if !solvable {
+                    match settings.fallback {
+                        NoGuessFallback::Random => {
+                            info!("No no-guess board found in {} attempts and {} ms, keeping a random board",
+                                attempts, search_start.elapsed().as_millis());
+                        }
+                        NoGuessFallback::Fail => {
+                            Err(ErrorKind::other("Could not generate a board that can be solved without guessing"))?;
+                        }
+                    }
                 }
             }
         }
@@ -146,6 +235,49 @@ impl Engine {
         Ok(())
     }
 
+    fn place_bombs(&mut self, candidate_cells: &mut [Cell], rng: &mut ChaCha8Rng) -> crossterm::Result<()> {
+        for state in self.board_state.values_mut() {
+            *state = Checked(Zero);
+        }
+        candidate_cells.shuffle(rng);
+        for cell in candidate_cells.iter().take(self.bomb_count as usize) {
+            self.board_state.insert(*cell, Bomb);
+            self.increment_bomb_count_of_surrounding_cells(*cell)?;
+        }
+        Ok(())
+    }
+
+    // Plays a copy of the game using only what the solver can prove, starting from the first click.
+    fn is_solvable_from(&self, clicked_cell: Cell) -> bool {
+        let mut trial = self.clone();
+        trial.board_initialised = true;
+        if let Complete(_) = trial.dig_cell(clicked_cell, true) {
+            return trial.checked_cells == trial.total_cells - trial.bomb_count;
+        }
+        loop {
+            let deductions = Solver::new(trial.width, trial.height, trial.bomb_count, &trial.board_play_state, true).solve();
+            let mut progress = false;
+            for cell in deductions.mine_cells {
+                if trial.board_play_state[&cell] == Unchecked {
+                    trial.flag_cell(cell);
+                    progress = true;
+                }
+            }
+            for cell in deductions.safe_cells {
+                if trial.board_play_state[&cell] == Unchecked {
+                    if let Complete(Lose) = trial.dig_cell(cell, false) {
+                        return false;
+                    }
+                    progress = true;
+                }
+            }
+            if !progress {
+                break;
+            }
+        }
+        trial.checked_cells == trial.total_cells - trial.bomb_count
+    }
+
     fn reveal_safe_patch(&mut self, starting_cell: Cell) -> crossterm::Result<()> {
         let mut visited_cells = HashSet::new();
         let mut cell_queue = queue![starting_cell];
@@ -193,7 +325,7 @@ impl Engine {
                 cells.push(cell);
             }
         }
-        return cells;
+        cells
     }
 
     fn dig_around_cell(&mut self, cell: Cell) -> GameState {
@@ -208,7 +340,7 @@ impl Engine {
                 }
             }
         }
-        return Playing;
+        Playing
     }
 
     fn flag_cell(&mut self, cell: Cell) -> GameState {
@@ -229,7 +361,7 @@ impl Engine {
             }
             _ => {}
         }
-        return Playing;
+        Playing
     }
 
     fn dig_cell(&mut self, cell: Cell, also_unflag: bool) -> GameState {
@@ -257,21 +389,53 @@ impl Engine {
                     _ => {}
                 }
             }
-            Flagged => {
-                if also_unflag {
-                    self.update_play_board_state(cell, Unchecked);
-                    self.flagged_cells -= 1;
-                }
+            Flagged if also_unflag => {
+                self.update_play_board_state(cell, Unchecked);
+                self.flagged_cells -= 1;
             }
             _ => {}
         }
-        return Playing;
+        Playing
     }
 
     fn update_play_board_state(&mut self, cell: Cell, new_state: CellState)
     {
-        self.board_play_state.insert(cell, new_state);
+        let old_state = self.board_play_state.insert(cell, new_state);
         self.changed_cells.insert(cell);
+        if let (Some(changes), Some(before)) = (self.pending_cell_changes.as_mut(), old_state) {
+            changes.push(CellChange {cell, before, after: new_state});
+        }
+    }
+
+    fn log_move(&mut self, action: ReplayAction) {
+        let elapsed_millis = match self.start_instant {
+            None => 0,
+            Some(instant) => instant.elapsed().as_millis() as u64
+        };
+        self.move_log.push(LoggedMove {elapsed_millis, action});
+    }
+
+    fn take_snapshot(&self) -> EngineSnapshot {
+        EngineSnapshot {
+            game_state: self.game_state,
+            checked_cells: self.checked_cells,
+            flagged_cells: self.flagged_cells,
+            game_complete_millis: self.game_complete_millis,
+        }
+    }
+
+    fn restore_snapshot(&mut self, snapshot: EngineSnapshot) {
+        self.game_state = snapshot.game_state;
+        self.checked_cells = snapshot.checked_cells;
+        self.flagged_cells = snapshot.flagged_cells;
+        self.game_complete_millis = snapshot.game_complete_millis;
+    }
+
+    fn restore_cells(&mut self, cells: impl Iterator<Item = (Cell, CellState)>) {
+        for (cell, state) in cells {
+            self.board_play_state.insert(cell, state);
+            self.changed_cells.insert(cell);
+        }
     }
 }
 
@@ -282,26 +446,26 @@ impl CanBeEngine for Engine {
 
     fn get_game_stats(&self) -> GameStats {
         let game_time = match self.game_state {
-            Complete(_) => self.game_complete_time,
+            Complete(_) => self.game_complete_millis,
             _ => match self.start_instant{
                 None => 0,
-                Some(instant) => instant.elapsed().as_secs()
+                Some(instant) => instant.elapsed().as_millis() as u64
             }
         };
-        GameStats {game_state: self.game_state, flags_remaining: self.bomb_count - self.flagged_cells, game_run_time: game_time}
+        GameStats {game_state: self.game_state, flags_remaining: self.bomb_count - self.flagged_cells, game_run_time_millis: game_time, used_undo: self.used_undo, no_guess_fallback: self.no_guess_fallback}
     }
 
     fn get_board_updates(&mut self) -> HashMap<Cell,CellState> {
         let mut cell_updates = HashMap::new();
         for cell in self.changed_cells.iter() {
-            cell_updates.insert(cell.clone(), self.board_play_state[cell]);
+            cell_updates.insert(*cell, self.board_play_state[cell]);
         }
         self.changed_cells.clear();
-        return cell_updates;
+        cell_updates
     }
 
     fn get_board_state(&mut self) -> HashMap<Cell,CellState> {
-        return self.board_play_state.clone();
+        self.board_play_state.clone()
     }
 
     fn play_move(&mut self, move_type: MoveType, cell: Cell) -> crossterm::Result<GameState> {
@@ -309,22 +473,24 @@ impl CanBeEngine for Engine {
             return Ok(Complete(state));
         }
 
-        if cell.x > self.width || cell.y > self.height {
-            Err(ErrorKind::new(
-                io::ErrorKind::Other,
+        if cell.x < 0 || cell.y < 0 || cell.x >= self.width || cell.y >= self.height {
+            Err(ErrorKind::other(
                 "Move location is out of range",
             ))?
         }
 
-        if self.bomb_count >= self.total_cells {
-            Err(ErrorKind::new(io::ErrorKind::Other, "Too many bombs! You can have a maximum of 1 bomb less than total cells"))?;
-        }
+        Engine::validate_settings(self.width, self.height, self.bomb_count)?;
 
-        if !self.board_initialised {
+        if self.start_instant.is_none() {
             self.start_instant = Some(Instant::now());
+        }
+        if !self.board_initialised {
             self.initialise_board(cell)?;
         }
+        self.log_move(ReplayAction::Move(move_type, cell));
 
+        let before = self.take_snapshot();
+        self.pending_cell_changes = Some(vec![]);
         let game_state = match move_type {
             Dig => {
                 self.dig_cell(cell, true)
@@ -336,12 +502,18 @@ impl CanBeEngine for Engine {
                 self.dig_around_cell(cell)
             }
         };
+        let cell_changes = self.pending_cell_changes.take().unwrap_or_default();
+        if !cell_changes.is_empty() {
+            self.history.record(HistoryEntry {before, after: self.take_snapshot(), cell_changes});
+        }
 
         Ok(game_state)
     }
 
     fn make_clone(&self) -> Box<dyn CanBeEngine> {
-        Box::from(Engine::new(self.width, self.height, self.bomb_count))
+        let mut engine = Engine::new(self.width, self.height, self.bomb_count);
+        engine.set_generation_mode(self.generation_mode);
+        Box::from(engine)
     }
 
     fn get_chill_factor(&mut self, cell: Cell) -> crossterm::Result<ZeroToEight> {
@@ -369,4 +541,185 @@ impl CanBeEngine for Engine {
         }
         ZeroToEight::from_u8(least_chill_value)
     }
+
+    fn get_hint(&mut self) -> crossterm::Result<Hint> {
+        Ok(Solver::new(self.width, self.height, self.bomb_count, &self.board_play_state, false).get_hint())
+    }
+
+    fn get_replay(&self) -> Option<Replay> {
+        if !self.board_initialised {
+            return None;
+        }
+        let mut mines: Vec<Cell> = self.board_state.iter()
+            .filter(|(_, state)| **state == Bomb)
+            .map(|(cell, _)| *cell)
+            .collect();
+        mines.sort_by_key(|c| (c.y, c.x));
+        Some(Replay {
+            width: self.width,
+            height: self.height,
+            bomb_count: self.bomb_count,
+            seed: self.seed,
+            mines,
+            moves: self.move_log.clone(),
+        })
+    }
+
+    fn undo(&mut self) -> crossterm::Result<GameState> {
+        if let Some(entry) = self.history.undo() {
+            self.log_move(ReplayAction::Undo);
+            self.restore_cells(entry.cell_changes.iter().rev().map(|c| (c.cell, c.before)));
+            self.restore_snapshot(entry.before);
+            self.used_undo = true;
+        }
+        Ok(self.game_state)
+    }
+
+    fn redo(&mut self) -> crossterm::Result<GameState> {
+        if let Some(entry) = self.history.redo() {
+            self.log_move(ReplayAction::Redo);
+            self.restore_cells(entry.cell_changes.iter().map(|c| (c.cell, c.after)));
+            self.restore_snapshot(entry.after);
+        }
+        Ok(self.game_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::generation_mode::NoGuessSettings;
+    use super::*;
+
+    fn mine_layout(engine: &Engine) -> Vec<Cell> {
+        engine.get_replay().expect("The board has been generated").mines
+    }
+
+    #[test]
+    fn same_seed_and_first_click_give_the_same_board() {
+        let mut first = Engine::new_with_seed(16, 16, 40, 1234);
+        let mut second = Engine::new_with_seed(16, 16, 40, 1234);
+        first.play_move(Dig, Cell {x: 7, y: 5}).unwrap();
+        second.play_move(Dig, Cell {x: 7, y: 5}).unwrap();
+        assert_eq!(mine_layout(&first), mine_layout(&second));
+        assert_eq!(first.get_board_state(), second.get_board_state());
+    }
+
+    #[test]
+    fn different_seeds_give_different_boards() {
+        let mut first = Engine::new_with_seed(16, 16, 40, 1);
+        let mut second = Engine::new_with_seed(16, 16, 40, 2);
+        first.play_move(Dig, Cell {x: 7, y: 5}).unwrap();
+        second.play_move(Dig, Cell {x: 7, y: 5}).unwrap();
+        assert_ne!(mine_layout(&first), mine_layout(&second));
+    }
+
+    fn no_guess_settings(max_time: Duration, fallback: NoGuessFallback) -> GenerationMode {
+        GenerationMode::NoGuess(NoGuessSettings { max_time, fallback, ..NoGuessSettings::default() })
+    }
+
+    #[test]
+    fn no_guess_board_is_solvable_from_the_first_click() {
+        let clicked = Cell {x: 4, y: 4};
+        for seed in 0..5 {
+            let mut engine = Engine::new_with_seed(9, 9, 10, seed);
+            engine.set_generation_mode(no_guess_settings(Duration::from_secs(60), NoGuessFallback::Fail));
+            engine.play_move(Dig, clicked).unwrap();
+            assert!(!engine.get_game_stats().no_guess_fallback);
+            let replayed = Engine::new_with_layout(9, 9, &mine_layout(&engine), seed).unwrap();
+            assert!(replayed.is_solvable_from(clicked));
+        }
+    }
+
+    #[test]
+    fn no_guess_search_stops_when_out_of_time() {
+        let clicked = Cell {x: 4, y: 4};
+        let mut engine = Engine::new_with_seed(9, 9, 60, 7);
+        engine.set_generation_mode(no_guess_settings(Duration::ZERO, NoGuessFallback::Fail));
+        assert!(engine.play_move(Dig, clicked).is_err());
+
+        let mut engine = Engine::new_with_seed(9, 9, 60, 7);
+        engine.set_generation_mode(no_guess_settings(Duration::ZERO, NoGuessFallback::Random));
+        engine.play_move(Dig, clicked).unwrap();
+        assert!(engine.get_game_stats().no_guess_fallback);
+        assert_eq!(mine_layout(&engine).len(), 60);
+    }
+
+    // Mines in the top left and bottom right corners of a 4x4 board.
+    fn corner_mines_engine() -> Engine {
+        Engine::new_with_layout(4, 4, &[Cell {x: 0, y: 0}, Cell {x: 3, y: 3}], 0).unwrap()
+    }
+
+    #[test]
+    fn undo_takes_back_a_losing_dig() {
+        let mut engine = corner_mines_engine();
+        engine.play_move(Dig, Cell {x: 1, y: 0}).unwrap();
+        let before_losing = engine.get_board_state();
+        assert_eq!(engine.play_move(Dig, Cell {x: 0, y: 0}).unwrap(), Complete(Lose));
+
+        assert_eq!(engine.undo().unwrap(), Playing);
+        assert_eq!(engine.get_board_state(), before_losing);
+        assert!(engine.get_game_stats().used_undo);
+
+        assert_eq!(engine.redo().unwrap(), Complete(Lose));
+        assert_eq!(engine.get_board_state()[&Cell {x: 0, y: 0}], Exploded);
+    }
+
+    #[test]
+    fn undo_takes_back_a_whole_cascade() {
+        let mut engine = corner_mines_engine();
+        let untouched = engine.get_board_state();
+        engine.play_move(Dig, Cell {x: 3, y: 0}).unwrap();
+        assert!(engine.get_board_state().values().filter(|s| **s != Unchecked).count() > 1);
+
+        assert_eq!(engine.undo().unwrap(), GameState::Initialised);
+        assert_eq!(engine.get_board_state(), untouched);
+        assert_eq!(engine.get_game_stats().flags_remaining, 2);
+    }
+
+    #[test]
+    fn undo_and_redo_of_flags_keep_the_flag_count() {
+        let mut engine = corner_mines_engine();
+        engine.play_move(Flag, Cell {x: 0, y: 0}).unwrap();
+        assert_eq!(engine.get_game_stats().flags_remaining, 1);
+        engine.undo().unwrap();
+        assert_eq!(engine.get_game_stats().flags_remaining, 2);
+        assert_eq!(engine.get_board_state()[&Cell {x: 0, y: 0}], Unchecked);
+        engine.redo().unwrap();
+        assert_eq!(engine.get_game_stats().flags_remaining, 1);
+        assert_eq!(engine.get_board_state()[&Cell {x: 0, y: 0}], Flagged);
+    }
+
+    #[test]
+    fn a_new_move_drops_what_was_undone() {
+        let mut engine = corner_mines_engine();
+        engine.play_move(Flag, Cell {x: 0, y: 0}).unwrap();
+        engine.undo().unwrap();
+        engine.play_move(Flag, Cell {x: 3, y: 3}).unwrap();
+        let state = engine.get_board_state();
+        engine.redo().unwrap();
+        assert_eq!(engine.get_board_state(), state);
+    }
+
+    #[test]
+    fn undo_with_nothing_to_undo_is_not_counted() {
+        let mut engine = corner_mines_engine();
+        assert_eq!(engine.undo().unwrap(), GameState::Initialised);
+        assert!(!engine.get_game_stats().used_undo);
+        engine.play_move(Flag, Cell {x: 0, y: 0}).unwrap();
+        engine.play_move(Flag, Cell {x: 3, y: 3}).unwrap();
+        engine.play_move(Dig, Cell {x: 3, y: 0}).unwrap();
+        assert_eq!(engine.play_move(Dig, Cell {x: 0, y: 3}).unwrap(), Complete(Win));
+        assert!(!engine.get_game_stats().used_undo);
+    }
+
+    #[test]
+    fn seeded_board_keeps_the_first_click_clear() {
+        let mut engine = Engine::new_with_seed(9, 9, 10, 99);
+        let clicked = Cell {x: 0, y: 8};
+        engine.play_move(Dig, clicked).unwrap();
+        let mines = mine_layout(&engine);
+        assert_eq!(mines.len(), 10);
+        assert!(mines.iter().all(|m| (m.x - clicked.x).abs() > 1 || (m.y - clicked.y).abs() > 1));
+    }
 }
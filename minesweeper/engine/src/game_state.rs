@@ -1,6 +1,6 @@
 use crate::CompleteState;
 
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub enum GameState {
     Initialised,
     #[default]
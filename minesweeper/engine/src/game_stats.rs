@@ -4,5 +4,9 @@ use crate::GameState;
 pub struct GameStats {
     pub game_state: GameState,
     pub flags_remaining: i32,
-    pub game_run_time: u64,
+    pub game_run_time_millis: u64,
+    // Games that used undo are left out of best times.
+    pub used_undo: bool,
+    // The board may need a guess, as no no-guess board was found in time.
+    pub no_guess_fallback: bool,
 }
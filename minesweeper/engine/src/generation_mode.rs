@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum GenerationMode {
+    #[default]
+    Random,
+    // Only accept layouts that the solver can finish from the first click without guessing.
+    NoGuess(NoGuessSettings),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct NoGuessSettings {
+    pub max_attempts: u32,
+    // Stops looking once this much time has gone, as big boards take a while to solve.
+    pub max_time: Duration,
+    pub fallback: NoGuessFallback,
+}
+
+impl Default for NoGuessSettings {
+    fn default() -> Self {
+        NoGuessSettings { max_attempts: 1000, max_time: Duration::from_secs(2), fallback: NoGuessFallback::Random }
+    }
+}
+
+// What to do when no solvable layout was found within max_attempts or max_time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NoGuessFallback {
+    // Keep the last generated layout, even though it may need a guess.
+    Random,
+    // Fail the first move with an error.
+    Fail,
+}
@@ -0,0 +1,13 @@
+use std::collections::{HashMap, HashSet};
+use crate::Cell;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Hint {
+    pub safe_cells: HashSet<Cell>,
+    pub mine_cells: HashSet<Cell>,
+    // The chance of each unknown cell being a mine, from 0.0 (certainly safe) to 1.0 (certainly a mine).
+    pub mine_probabilities: HashMap<Cell, f64>,
+    // Cells on parts of the frontier too big to work out exactly, whose probability is only an
+    // estimate.
+    pub estimated_cells: HashSet<Cell>,
+}
@@ -21,3 +21,12 @@ pub mod complete_state;
 pub mod game_state;
 pub mod game_stats;
 pub mod engine;
+pub mod generation_mode;
+pub mod solver;
+pub mod deductions;
+pub mod hint;
+mod move_history;
+pub mod replay_action;
+pub mod logged_move;
+pub mod replay;
+pub mod replay_player;
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use crate::replay_action::ReplayAction;
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoggedMove {
+    // Time since the first move of the game.
+    pub elapsed_millis: u64,
+    pub action: ReplayAction,
+}
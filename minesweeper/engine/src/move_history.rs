@@ -0,0 +1,51 @@
+use crate::{Cell, CellState, GameState};
+
+// The parts of the engine, apart from the board itself, that a move can change.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct EngineSnapshot {
+    pub game_state: GameState,
+    pub checked_cells: i32,
+    pub flagged_cells: i32,
+    pub game_complete_millis: u64,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct CellChange {
+    pub cell: Cell,
+    pub before: CellState,
+    pub after: CellState,
+}
+
+// A single play_move, including any cascade it caused, so it can be taken back or played again.
+#[derive(Debug, Clone)]
+pub(crate) struct HistoryEntry {
+    pub before: EngineSnapshot,
+    pub after: EngineSnapshot,
+    pub cell_changes: Vec<CellChange>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MoveHistory {
+    undo_stack: Vec<HistoryEntry>,
+    redo_stack: Vec<HistoryEntry>,
+}
+
+impl MoveHistory {
+    // A new move makes anything that was undone unreachable, so the redo stack is dropped.
+    pub fn record(&mut self, entry: HistoryEntry) {
+        self.undo_stack.push(entry);
+        self.redo_stack.clear();
+    }
+
+    pub fn undo(&mut self) -> Option<HistoryEntry> {
+        let entry = self.undo_stack.pop()?;
+        self.redo_stack.push(entry.clone());
+        Some(entry)
+    }
+
+    pub fn redo(&mut self) -> Option<HistoryEntry> {
+        let entry = self.redo_stack.pop()?;
+        self.undo_stack.push(entry.clone());
+        Some(entry)
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use crate::Cell;
+use crate::logged_move::LoggedMove;
+
+// Everything needed to play a game back. The mine layout is stored as well as the seed, so a
+// replay does not depend on how boards are generated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub width: i32,
+    pub height: i32,
+    pub bomb_count: i32,
+    pub seed: u64,
+    pub mines: Vec<Cell>,
+    pub moves: Vec<LoggedMove>,
+}
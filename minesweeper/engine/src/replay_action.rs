@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use crate::{Cell, MoveType};
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ReplayAction {
+    Move(MoveType, Cell),
+    Undo,
+    Redo,
+}
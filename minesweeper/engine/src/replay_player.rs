@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use crate::engine::Engine;
+use crate::hint::Hint;
+use crate::replay::Replay;
+use crate::replay_action::ReplayAction;
+use crate::{CanBeEngine, Cell, CellState, Complete, GameState, GameStats, MoveType, ZeroToEight};
+
+// Plays a recorded game back through a real Engine as time passes. Moves from the player are ignored.
+pub struct ReplayPlayer {
+    replay: Replay,
+    engine: Engine,
+    speed: f64,
+    next_move: usize,
+    // Replay time reached when the speed last changed, and the instant it changed.
+    position: Duration,
+    position_instant: Instant,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay, speed: f64) -> crossterm::Result<Self> {
+        let engine = Engine::new_with_layout(replay.width, replay.height, &replay.mines, replay.seed)?;
+        Ok(ReplayPlayer {
+            replay,
+            engine,
+            speed,
+            next_move: 0,
+            position: Duration::ZERO,
+            position_instant: Instant::now(),
+        })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_move >= self.replay.moves.len()
+    }
+
+    fn get_position(&self) -> Duration {
+        self.position + self.position_instant.elapsed().mul_f64(self.speed)
+    }
+
+    // Plays every recorded move that is due at the current replay position.
+    fn advance(&mut self) -> crossterm::Result<()> {
+        let position = self.get_position().as_millis() as u64;
+        while let Some(logged_move) = self.replay.moves.get(self.next_move) {
+            if logged_move.elapsed_millis > position {
+                break;
+            }
+            match logged_move.action {
+                ReplayAction::Move(move_type, cell) => self.engine.play_move(move_type, cell)?,
+                ReplayAction::Undo => self.engine.undo()?,
+                ReplayAction::Redo => self.engine.redo()?,
+            };
+            self.next_move += 1;
+        }
+        Ok(())
+    }
+
+    fn advance_or_log(&mut self) {
+        if let Err(e) = self.advance() {
+            log::error!("Replay stopped: {}", e);
+            self.next_move = self.replay.moves.len();
+        }
+    }
+}
+
+impl CanBeEngine for ReplayPlayer {
+    fn get_size(&self) -> (i32, i32) {
+        self.engine.get_size()
+    }
+
+    // The clock follows the replay rather than the wall clock, so it speeds up with the replay.
+    fn get_game_stats(&self) -> GameStats {
+        let mut game_stats = self.engine.get_game_stats();
+        let last_move_millis = self.replay.moves.last().map_or(0, |m| m.elapsed_millis);
+        let position_millis = self.get_position().as_millis() as u64;
+        game_stats.game_run_time_millis = match game_stats.game_state {
+            Complete(_) => last_move_millis,
+            _ => position_millis.min(last_move_millis),
+        };
+        game_stats
+    }
+
+    fn get_board_updates(&mut self) -> HashMap<Cell, CellState> {
+        self.advance_or_log();
+        self.engine.get_board_updates()
+    }
+
+    fn get_board_state(&mut self) -> HashMap<Cell, CellState> {
+        self.advance_or_log();
+        self.engine.get_board_state()
+    }
+
+    fn play_move(&mut self, _: MoveType, _: Cell) -> crossterm::Result<GameState> {
+        Ok(self.engine.get_game_stats().game_state)
+    }
+
+    // Starts the same replay again from the beginning.
+    fn make_clone(&self) -> Box<dyn CanBeEngine> {
+        match ReplayPlayer::new(self.replay.clone(), self.speed) {
+            Ok(player) => Box::from(player),
+            Err(_) => Box::from(Engine::new(self.replay.width, self.replay.height, self.replay.bomb_count)),
+        }
+    }
+
+    fn get_chill_factor(&mut self, cell: Cell) -> crossterm::Result<ZeroToEight> {
+        self.engine.get_chill_factor(cell)
+    }
+
+    fn get_hint(&mut self) -> crossterm::Result<Hint> {
+        self.engine.get_hint()
+    }
+
+    fn get_replay(&self) -> Option<Replay> {
+        None
+    }
+
+    fn undo(&mut self) -> crossterm::Result<GameState> {
+        Ok(self.engine.get_game_stats().game_state)
+    }
+
+    fn redo(&mut self) -> crossterm::Result<GameState> {
+        Ok(self.engine.get_game_stats().game_state)
+    }
+
+    fn get_speed(&self) -> Option<f64> {
+        Some(self.speed)
+    }
+
+    // Carries on from the current position, so changing speed never jumps in the replay.
+    fn set_speed(&mut self, speed: f64) {
+        self.position = self.get_position();
+        self.position_instant = Instant::now();
+        self.speed = speed;
+    }
+}
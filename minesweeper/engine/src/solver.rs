@@ -0,0 +1,601 @@
+use std::collections::{HashMap, HashSet};
+use crate::{Bomb, Cell, CellState, Checked, Exploded, Flagged, Unchecked};
+use crate::deductions::Deductions;
+use crate::hint::Hint;
+
+// Frontier components bigger than this, or that take more steps than this to search, are
+// left to an estimate instead of being enumerated exactly.
+const MAX_ENUMERATION_CELLS: usize = 48;
+const MAX_ENUMERATION_STEPS: usize = 250_000;
+
+// The unknown cells around a revealed number and how many mines are still hidden among them.
+#[derive(Debug, Clone)]
+struct Constraint {
+    cells: HashSet<Cell>,
+    mines: usize,
+}
+
+// A group of frontier cells that share constraints, so it can be enumerated on its own.
+struct Component {
+    cells: Vec<Cell>,
+    constraints: Vec<(Vec<usize>, usize)>,
+}
+
+// solutions[k] is the number of ways the component can hold k mines and cell_mines[k][i]
+// how many of those have a mine on cell i.
+struct ComponentSolutions {
+    cells: Vec<Cell>,
+    solutions: Vec<f64>,
+    cell_mines: Vec<Vec<f64>>,
+}
+
+// What enumerating the frontier found out about the undecided cells.
+struct Frontier {
+    mine_probabilities: HashMap<Cell, f64>,
+    // Cells that are safe or mines in every arrangement.
+    certain: Deductions,
+    // Cells of components that were too big to enumerate, whose probability is only the
+    // density of the mines left.
+    estimated_cells: HashSet<Cell>,
+}
+
+struct Enumeration<'c> {
+    component: &'c Component,
+    cell_constraints: Vec<Vec<usize>>,
+    placed_mines: Vec<usize>,
+    unassigned_cells: Vec<usize>,
+    assignment: Vec<bool>,
+    max_mines: usize,
+    steps: usize,
+    result: ComponentSolutions,
+}
+
+// Works only from what the player can see, so it never peeks at where the mines really are.
+pub struct Solver<'a> {
+    width: i32,
+    height: i32,
+    bomb_count: i32,
+    board_play_state: &'a HashMap<Cell, CellState>,
+    trust_flags: bool,
+}
+
+impl<'a> Solver<'a> {
+    // When trust_flags is false, flagged cells are treated as unknown, as a player's flags may be wrong.
+    pub fn new(width: i32, height: i32, bomb_count: i32, board_play_state: &'a HashMap<Cell, CellState>, trust_flags: bool) -> Self {
+        Solver { width, height, bomb_count, board_play_state, trust_flags }
+    }
+
+    pub fn solve(&self) -> Deductions {
+        let mut deductions = Deductions::default();
+        for (cell, state) in self.board_play_state.iter() {
+            if self.is_known_mine(*state) {
+                deductions.mine_cells.insert(*cell);
+            }
+        }
+
+        loop {
+            let constraints = self.build_constraints(&deductions);
+            if self.apply_single_cell_rule(&constraints, &mut deductions) {
+                continue;
+            }
+            if self.apply_subset_rule(&constraints, &mut deductions) {
+                continue;
+            }
+            if self.apply_mine_count_rule(&mut deductions) {
+                continue;
+            }
+            let certain = self.enumerate_frontier(&deductions).certain;
+            let safe_progress = Solver::mark(certain.safe_cells.into_iter(), &mut deductions.safe_cells);
+            let mine_progress = Solver::mark(certain.mine_cells.into_iter(), &mut deductions.mine_cells);
+            if !safe_progress && !mine_progress {
+                break;
+            }
+        }
+
+        deductions.mine_cells.retain(|c| self.is_unknown(self.board_play_state[c]));
+        deductions
+    }
+
+    pub fn get_hint(&self) -> Hint {
+        let deductions = self.solve();
+        let mut known_deductions = deductions.clone();
+        for (cell, state) in self.board_play_state.iter() {
+            if self.is_known_mine(*state) {
+                known_deductions.mine_cells.insert(*cell);
+            }
+        }
+        let Frontier { mut mine_probabilities, estimated_cells, .. } = self.enumerate_frontier(&known_deductions);
+        for cell in deductions.safe_cells.iter() {
+            mine_probabilities.insert(*cell, 0.0);
+        }
+        for cell in deductions.mine_cells.iter() {
+            mine_probabilities.insert(*cell, 1.0);
+        }
+        Hint {
+            safe_cells: deductions.safe_cells,
+            mine_cells: deductions.mine_cells,
+            mine_probabilities,
+            estimated_cells,
+        }
+    }
+
+    fn is_known_mine(&self, state: CellState) -> bool {
+        match state {
+            Bomb | Exploded => true,
+            Flagged => self.trust_flags,
+            _ => false,
+        }
+    }
+
+    fn is_unknown(&self, state: CellState) -> bool {
+        match state {
+            Unchecked => true,
+            Flagged => !self.trust_flags,
+            _ => false,
+        }
+    }
+
+    fn is_undecided(&self, cell: &Cell, deductions: &Deductions) -> bool {
+        self.is_unknown(self.board_play_state[cell]) &&
+            !deductions.safe_cells.contains(cell) &&
+            !deductions.mine_cells.contains(cell)
+    }
+
+    fn get_surrounding_cells(&self, cell: Cell, distance: i32) -> Vec<Cell> {
+        let mut cells = vec![];
+        for x in (cell.x - distance)..(cell.x + distance + 1) {
+            for y in (cell.y - distance)..(cell.y + distance + 1) {
+                if x < 0 || x >= self.width || y < 0 || y >= self.height || (x == cell.x && y == cell.y) {
+                    continue;
+                }
+                cells.push(Cell {x, y});
+            }
+        }
+        cells
+    }
+
+    fn build_constraints(&self, deductions: &Deductions) -> HashMap<Cell, Constraint> {
+        let mut constraints = HashMap::new();
+        for (cell, state) in self.board_play_state.iter() {
+            if let Checked(adjacent_bombs) = state {
+                let mut cells = HashSet::new();
+                let mut mines = adjacent_bombs.to_usize();
+                for surrounding_cell in self.get_surrounding_cells(*cell, 1) {
+                    if deductions.mine_cells.contains(&surrounding_cell) {
+                        mines = mines.saturating_sub(1);
+                    } else if self.is_undecided(&surrounding_cell, deductions) {
+                        cells.insert(surrounding_cell);
+                    }
+                }
+                if !cells.is_empty() {
+                    constraints.insert(*cell, Constraint { cells, mines });
+                }
+            }
+        }
+        constraints
+    }
+
+    fn mark(cells: impl Iterator<Item = Cell>, target: &mut HashSet<Cell>) -> bool {
+        let mut progress = false;
+        for cell in cells {
+            progress |= target.insert(cell);
+        }
+        progress
+    }
+
+    // A number that is already satisfied makes all its unknown neighbours safe, and a number
+    // that needs every unknown neighbour makes them all mines.
+    fn apply_single_cell_rule(&self, constraints: &HashMap<Cell, Constraint>, deductions: &mut Deductions) -> bool {
+        let mut progress = false;
+        for constraint in constraints.values() {
+            if constraint.mines == 0 {
+                progress |= Solver::mark(constraint.cells.iter().copied(), &mut deductions.safe_cells);
+            } else if constraint.mines == constraint.cells.len() {
+                progress |= Solver::mark(constraint.cells.iter().copied(), &mut deductions.mine_cells);
+            }
+        }
+        progress
+    }
+
+    // When one constraint's cells are contained in another's, the cells only in the larger one
+    // hold exactly the difference in mines.
+    fn apply_subset_rule(&self, constraints: &HashMap<Cell, Constraint>, deductions: &mut Deductions) -> bool {
+        let mut progress = false;
+        for (source, constraint) in constraints.iter() {
+            // Only numbers within two cells of each other can share unknown neighbours.
+            for other_source in self.get_surrounding_cells(*source, 2) {
+                let other = match constraints.get(&other_source) {
+                    Some(other) => other,
+                    None => continue,
+                };
+                if other.cells.len() <= constraint.cells.len() || !constraint.cells.is_subset(&other.cells) {
+                    continue;
+                }
+                let difference = other.cells.difference(&constraint.cells).copied();
+                let difference_mines = other.mines.saturating_sub(constraint.mines);
+                if difference_mines == 0 {
+                    progress |= Solver::mark(difference, &mut deductions.safe_cells);
+                } else if difference_mines == other.cells.len() - constraint.cells.len() {
+                    progress |= Solver::mark(difference, &mut deductions.mine_cells);
+                }
+            }
+        }
+        progress
+    }
+
+    // Once every mine is accounted for the rest is safe, and if only as many cells as mines
+    // remain they must all be mines.
+    fn apply_mine_count_rule(&self, deductions: &mut Deductions) -> bool {
+        let undecided_cells: Vec<Cell> = self.board_play_state.keys()
+            .filter(|c| self.is_undecided(c, deductions))
+            .copied()
+            .collect();
+        if undecided_cells.is_empty() {
+            return false;
+        }
+        let remaining_mines = (self.bomb_count as usize).saturating_sub(deductions.mine_cells.len());
+        if remaining_mines == 0 {
+            Solver::mark(undecided_cells.into_iter(), &mut deductions.safe_cells)
+        } else if remaining_mines == undecided_cells.len() {
+            Solver::mark(undecided_cells.into_iter(), &mut deductions.mine_cells)
+        } else {
+            false
+        }
+    }
+
+    // Counts every mine arrangement of the frontier that agrees with the numbers and the total
+    // mine count. The cells of components that are too big to enumerate lose their constraints,
+    // so they are never reported as certain.
+    fn enumerate_frontier(&self, deductions: &Deductions) -> Frontier {
+        let constraints = self.build_constraints(deductions);
+        let undecided_cells: Vec<Cell> = self.board_play_state.keys()
+            .filter(|c| self.is_undecided(c, deductions))
+            .copied()
+            .collect();
+        let remaining_mines = (self.bomb_count as usize).saturating_sub(deductions.mine_cells.len());
+
+        let mut enumerated = vec![];
+        let mut enumerated_cells = HashSet::new();
+        let mut estimated_cells = HashSet::new();
+        for component in Solver::split_into_components(&constraints) {
+            match Solver::enumerate_component(&component, remaining_mines) {
+                Some(result) => {
+                    enumerated_cells.extend(result.cells.iter().copied());
+                    enumerated.push(result);
+                }
+                None => estimated_cells.extend(component.cells.iter().copied()),
+            }
+        }
+        let other_cells: Vec<Cell> = undecided_cells.iter()
+            .filter(|c| !enumerated_cells.contains(c))
+            .copied()
+            .collect();
+
+        let mut mine_probabilities = HashMap::new();
+        let mut certain = Deductions::default();
+        let distributions: Vec<&Vec<f64>> = enumerated.iter().map(|r| &r.solutions).collect();
+        let total = Solver::convolve(&distributions);
+        let weights = Solver::other_cell_weights(total.len(), other_cells.len(), remaining_mines);
+        let normaliser: f64 = total.iter().enumerate().map(|(k, count)| count * weights[k]).sum();
+        if normaliser <= 0.0 {
+            // The visible board contradicts itself (for example after a loss), so just spread the mines evenly.
+            for cell in undecided_cells.iter() {
+                mine_probabilities.insert(*cell, remaining_mines as f64 / undecided_cells.len() as f64);
+            }
+            return Frontier { mine_probabilities, certain, estimated_cells };
+        }
+
+        for (i, result) in enumerated.iter().enumerate() {
+            let others: Vec<&Vec<f64>> = distributions.iter().enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, d)| *d)
+                .collect();
+            let rest = Solver::convolve(&others);
+            let component_weights: Vec<f64> = (0..result.solutions.len())
+                .map(|k| rest.iter().enumerate().map(|(r, count)| count * weights[k + r]).sum())
+                .collect();
+            for (index, cell) in result.cells.iter().enumerate() {
+                let mut mine_weight = 0.0;
+                let mut safe_weight = 0.0;
+                for (k, weight) in component_weights.iter().enumerate() {
+                    mine_weight += result.cell_mines[k][index] * weight;
+                    safe_weight += (result.solutions[k] - result.cell_mines[k][index]) * weight;
+                }
+                if mine_weight == 0.0 {
+                    certain.safe_cells.insert(*cell);
+                } else if safe_weight == 0.0 {
+                    certain.mine_cells.insert(*cell);
+                }
+                mine_probabilities.insert(*cell, mine_weight / normaliser);
+            }
+        }
+
+        if !other_cells.is_empty() {
+            let mut mine_weight = 0.0;
+            let mut safe_weight = 0.0;
+            for (k, count) in total.iter().enumerate() {
+                let other_mines = remaining_mines.saturating_sub(k) as f64;
+                mine_weight += count * weights[k] * other_mines;
+                safe_weight += count * weights[k] * (other_cells.len() as f64 - other_mines);
+            }
+            let probability = mine_weight / (normaliser * other_cells.len() as f64);
+            for cell in other_cells {
+                // The numbers next to an estimated cell were left out of the count.
+                if !estimated_cells.contains(&cell) {
+                    if mine_weight == 0.0 {
+                        certain.safe_cells.insert(cell);
+                    } else if safe_weight == 0.0 {
+                        certain.mine_cells.insert(cell);
+                    }
+                }
+                mine_probabilities.insert(cell, probability);
+            }
+        }
+        Frontier { mine_probabilities, certain, estimated_cells }
+    }
+
+    fn split_into_components(constraints: &HashMap<Cell, Constraint>) -> Vec<Component> {
+        let mut cell_indices: HashMap<Cell, usize> = HashMap::new();
+        let mut cells = vec![];
+        for constraint in constraints.values() {
+            for cell in constraint.cells.iter() {
+                if !cell_indices.contains_key(cell) {
+                    cell_indices.insert(*cell, cells.len());
+                    cells.push(*cell);
+                }
+            }
+        }
+
+        let mut parents: Vec<usize> = (0..cells.len()).collect();
+        fn find(parents: &mut [usize], index: usize) -> usize {
+            let mut root = index;
+            while parents[root] != root {
+                root = parents[root];
+            }
+            parents[index] = root;
+            root
+        }
+        for constraint in constraints.values() {
+            let mut constraint_cells = constraint.cells.iter();
+            if let Some(first) = constraint_cells.next() {
+                let first_root = find(&mut parents, cell_indices[first]);
+                for cell in constraint_cells {
+                    let root = find(&mut parents, cell_indices[cell]);
+                    parents[root] = first_root;
+                }
+            }
+        }
+
+        let mut components: HashMap<usize, Component> = HashMap::new();
+        let mut local_indices = vec![0; cells.len()];
+        // Adding cells constraint by constraint keeps neighbouring cells close together in the
+        // search order, which lets the enumeration prune early.
+        for constraint in constraints.values() {
+            for cell in constraint.cells.iter() {
+                let index = cell_indices[cell];
+                let root = find(&mut parents, index);
+                let component = components.entry(root).or_insert_with(|| Component { cells: vec![], constraints: vec![] });
+                if !component.cells.contains(cell) {
+                    local_indices[index] = component.cells.len();
+                    component.cells.push(*cell);
+                }
+            }
+        }
+        for constraint in constraints.values() {
+            if let Some(first) = constraint.cells.iter().next() {
+                let root = find(&mut parents, cell_indices[first]);
+                let indices = constraint.cells.iter().map(|c| local_indices[cell_indices[c]]).collect();
+                if let Some(component) = components.get_mut(&root) {
+                    component.constraints.push((indices, constraint.mines));
+                }
+            }
+        }
+        components.into_values().collect()
+    }
+
+    fn enumerate_component(component: &Component, max_mines: usize) -> Option<ComponentSolutions> {
+        if component.cells.len() > MAX_ENUMERATION_CELLS {
+            return None;
+        }
+        let mut cell_constraints = vec![vec![]; component.cells.len()];
+        let mut unassigned_cells = vec![];
+        for (constraint_index, (indices, _)) in component.constraints.iter().enumerate() {
+            for index in indices {
+                cell_constraints[*index].push(constraint_index);
+            }
+            unassigned_cells.push(indices.len());
+        }
+        let mut enumeration = Enumeration {
+            component,
+            cell_constraints,
+            placed_mines: vec![0; component.constraints.len()],
+            unassigned_cells,
+            assignment: vec![false; component.cells.len()],
+            max_mines,
+            steps: 0,
+            result: ComponentSolutions {
+                cells: component.cells.clone(),
+                solutions: vec![0.0; component.cells.len() + 1],
+                cell_mines: vec![vec![0.0; component.cells.len()]; component.cells.len() + 1],
+            },
+        };
+        if enumeration.search(0, 0) {
+            Some(enumeration.result)
+        } else {
+            None
+        }
+    }
+
+    // Combines the mine count distributions of independent components.
+    fn convolve(distributions: &[&Vec<f64>]) -> Vec<f64> {
+        let mut total = vec![1.0];
+        for distribution in distributions {
+            let mut combined = vec![0.0; total.len() + distribution.len() - 1];
+            for (i, a) in total.iter().enumerate() {
+                if *a == 0.0 {
+                    continue;
+                }
+                for (j, b) in distribution.iter().enumerate() {
+                    combined[i + j] += a * b;
+                }
+            }
+            total = combined;
+        }
+        total
+    }
+
+    // weights[k] is proportional to the number of ways the cells off the frontier can hold the
+    // mines left over when the frontier holds k of them.
+    fn other_cell_weights(length: usize, other_cells: usize, remaining_mines: usize) -> Vec<f64> {
+        let ln_weights: Vec<Option<f64>> = (0..length)
+            .map(|k| {
+                if k > remaining_mines || remaining_mines - k > other_cells {
+                    None
+                } else {
+                    Some(Solver::ln_choose(other_cells, remaining_mines - k))
+                }
+            })
+            .collect();
+        let max = ln_weights.iter().flatten().fold(f64::NEG_INFINITY, |a, b| a.max(*b));
+        ln_weights.iter().map(|w| w.map_or(0.0, |w| (w - max).exp())).collect()
+    }
+
+    fn ln_choose(n: usize, r: usize) -> f64 {
+        let r = r.min(n - r);
+        (1..r + 1).map(|i| ((n - r + i) as f64).ln() - (i as f64).ln()).sum()
+    }
+}
+
+impl<'c> Enumeration<'c> {
+    // Returns false when the step budget runs out.
+    fn search(&mut self, index: usize, mines: usize) -> bool {
+        self.steps += 1;
+        if self.steps > MAX_ENUMERATION_STEPS {
+            return false;
+        }
+        if index == self.assignment.len() {
+            self.result.solutions[mines] += 1.0;
+            for (i, is_mine) in self.assignment.iter().enumerate() {
+                if *is_mine {
+                    self.result.cell_mines[mines][i] += 1.0;
+                }
+            }
+            return true;
+        }
+
+        for is_mine in [false, true] {
+            if is_mine && mines >= self.max_mines {
+                continue;
+            }
+            let fits = self.cell_constraints[index].iter().all(|c| {
+                let placed = self.placed_mines[*c] + is_mine as usize;
+                let needed = self.component.constraints[*c].1;
+                placed <= needed && placed + self.unassigned_cells[*c] > needed
+            });
+            if !fits {
+                continue;
+            }
+            for c in self.cell_constraints[index].iter() {
+                self.placed_mines[*c] += is_mine as usize;
+                self.unassigned_cells[*c] -= 1;
+            }
+            self.assignment[index] = is_mine;
+            let completed = self.search(index + 1, mines + is_mine as usize);
+            self.assignment[index] = false;
+            for c in self.cell_constraints[index].iter() {
+                self.placed_mines[*c] -= is_mine as usize;
+                self.unassigned_cells[*c] += 1;
+            }
+            if !completed {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{One, Three};
+    use super::*;
+
+    // Every cell not given is unchecked.
+    fn board(width: i32, height: i32, cells: &[(i32, i32, CellState)]) -> HashMap<Cell, CellState> {
+        let mut board = HashMap::new();
+        for x in 0..width {
+            for y in 0..height {
+                board.insert(Cell {x, y}, Unchecked);
+            }
+        }
+        for (x, y, state) in cells {
+            board.insert(Cell {x: *x, y: *y}, *state);
+        }
+        board
+    }
+
+    fn cells(cells: &[(i32, i32)]) -> HashSet<Cell> {
+        cells.iter().map(|(x, y)| Cell {x: *x, y: *y}).collect()
+    }
+
+    #[test]
+    fn satisfied_number_makes_its_neighbours_safe() {
+        let board = board(3, 3, &[(1, 1, Checked(One)), (0, 0, Flagged)]);
+        let deductions = Solver::new(3, 3, 1, &board, true).solve();
+        assert_eq!(deductions.safe_cells, cells(&[(1, 0), (2, 0), (0, 1), (2, 1), (0, 2), (1, 2), (2, 2)]));
+        assert!(deductions.mine_cells.is_empty());
+    }
+
+    #[test]
+    fn number_needing_every_neighbour_makes_them_mines() {
+        let board = board(2, 2, &[(0, 0, Checked(Three))]);
+        let deductions = Solver::new(2, 2, 3, &board, true).solve();
+        assert_eq!(deductions.mine_cells, cells(&[(1, 0), (0, 1), (1, 1)]));
+        assert!(deductions.safe_cells.is_empty());
+    }
+
+    #[test]
+    fn untrusted_flag_is_treated_as_unknown() {
+        // The flag on (1, 0) may be wrong, so the mine can be on either unknown cell.
+        let board = board(2, 2, &[(0, 0, Checked(One)), (1, 1, Checked(One)), (1, 0, Flagged)]);
+        let hint = Solver::new(2, 2, 1, &board, false).get_hint();
+        assert!(hint.safe_cells.is_empty());
+        assert!(hint.mine_cells.is_empty());
+        assert_eq!(hint.mine_probabilities[&Cell {x: 1, y: 0}], 0.5);
+        assert!(hint.estimated_cells.is_empty());
+    }
+
+    #[test]
+    fn enumeration_finds_certain_cells() {
+        // Only a mine on (1, 0) fits the ones around the two unknown cells.
+        let board = board(3, 2, &[(0, 0, Checked(One)), (0, 1, Checked(One)), (2, 1, Checked(One)), (1, 1, Checked(One))]);
+        let solver = Solver::new(3, 2, 1, &board, true);
+        let frontier = solver.enumerate_frontier(&Deductions::default());
+        assert_eq!(frontier.certain.mine_cells, cells(&[(1, 0)]));
+        assert_eq!(frontier.certain.safe_cells, cells(&[(2, 0)]));
+        assert!(frontier.estimated_cells.is_empty());
+    }
+
+    #[test]
+    fn skipped_component_is_never_certain() {
+        // A row of ones over a row of unknown cells is one component too big to enumerate.
+        let width = MAX_ENUMERATION_CELLS as i32 + 12;
+        let numbers: Vec<(i32, i32, CellState)> = (0..width).map(|x| (x, 0, Checked(One))).collect();
+        let board = board(width, 2, &numbers);
+        // With no mines left, the cells would be certainly safe if their numbers were ignored.
+        let solver = Solver::new(width, 2, 0, &board, true);
+        let frontier = solver.enumerate_frontier(&Deductions::default());
+        let row: HashSet<Cell> = (0..width).map(|x| Cell {x, y: 1}).collect();
+        assert_eq!(frontier.estimated_cells, row);
+        assert!(frontier.certain.safe_cells.is_empty());
+        assert!(frontier.certain.mine_cells.is_empty());
+
+        let solver = Solver::new(width, 2, width / 3, &board, true);
+        let frontier = solver.enumerate_frontier(&Deductions::default());
+        assert_eq!(frontier.estimated_cells, row);
+        assert!(frontier.certain.safe_cells.is_empty());
+        assert!(frontier.certain.mine_cells.is_empty());
+        for cell in row.iter() {
+            assert_eq!(frontier.mine_probabilities[cell], (width / 3) as f64 / width as f64);
+        }
+    }
+}
@@ -1,5 +1,4 @@
 use crossterm::ErrorKind;
-use std::io;
 use crate::{Eight, Five, Four, One, Seven, Six, Three, Two, Zero};
 
 #[derive(Debug, Copy, Clone, PartialEq, Default)]
@@ -28,7 +27,7 @@ impl ZeroToEight {
             6 => Six,
             7 => Seven,
             8 => Eight,
-            _ => Err(ErrorKind::new(io::ErrorKind::Other, "This number cannot be an adjacent number of bombs!"))?
+            _ => Err(ErrorKind::other("This number cannot be an adjacent number of bombs!"))?
         };
         Ok(bombs)
     }
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use crate::date::format_date;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BestTime {
+    // Version 1 statistics kept whole seconds here.
+    #[serde(alias = "time")]
+    pub time_millis: u64,
+    // When the game was won, in seconds since the Unix epoch. None for times kept before
+    // there were statistics.
+    pub date: Option<u64>,
+}
+
+impl BestTime {
+    pub fn format_date(&self) -> String {
+        match self.date {
+            Some(date) => format_date(date),
+            None => "-".to_string(),
+        }
+    }
+}
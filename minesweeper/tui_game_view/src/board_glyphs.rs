@@ -0,0 +1,137 @@
+use game_actions::config::glyph_set::GlyphSet;
+use game_actions::config::minesweeper_glyphs::MinesweeperGlyphs;
+use minesweeper_engine::zero_to_eight::ZeroToEight;
+
+const EMOJI_CHILL_FACES: [&str; 9] = ["😊", "🙂", "😐", "😕", "😟", "😩", "😱", "🤯", "🙃"];
+const TEXT_CHILL_FACES: [&str; 9] = [":D", ":)", ":|", ":/", ":(", ";(", ":O", "8O", "(:"];
+
+// Everything a GameView draws with in one glyph set. Cell glyphs and digits fill cell_width
+// columns, faces always fill two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardGlyphs {
+    pub glyph_set: GlyphSet,
+    pub cell_width: i32,
+    pub unchecked: char,
+    pub empty: char,
+    pub flag: char,
+    pub mine: char,
+    pub wrong_flag: char,
+    pub exploded: char,
+    pub safe_hint: char,
+    // A hint that is only the least risky guess.
+    pub guess_hint: char,
+    pub flag_counter: char,
+    pub clock: char,
+    pub trophy: char,
+    pub horizontal_line: char,
+    pub vertical_line: char,
+    // Shown before the first move.
+    pub waiting_face: &'static str,
+    pub win_face: &'static str,
+    pub lose_face: &'static str,
+    chill_faces: [&'static str; 9],
+}
+
+impl BoardGlyphs {
+    pub fn new(glyph_set: GlyphSet, overrides: &MinesweeperGlyphs) -> BoardGlyphs {
+        let glyphs = match glyph_set {
+            GlyphSet::Emoji => BoardGlyphs::emoji(),
+            GlyphSet::Unicode => BoardGlyphs::unicode(),
+            GlyphSet::Ascii => BoardGlyphs::ascii(),
+        };
+        let get = |glyph: Option<char>, default: char| MinesweeperGlyphs::get(glyph, glyph_set).unwrap_or(default);
+        BoardGlyphs {
+            unchecked: get(overrides.unchecked, glyphs.unchecked),
+            empty: get(overrides.empty, glyphs.empty),
+            flag: get(overrides.flag, glyphs.flag),
+            mine: get(overrides.mine, glyphs.mine),
+            wrong_flag: get(overrides.wrong_flag, glyphs.wrong_flag),
+            exploded: get(overrides.exploded, glyphs.exploded),
+            ..glyphs
+        }
+    }
+
+    fn emoji() -> BoardGlyphs {
+        BoardGlyphs {
+            glyph_set: GlyphSet::Emoji,
+            cell_width: 2,
+            unchecked: '🟩',
+            empty: '🟫',
+            flag: '🚩',
+            mine: '💣',
+            wrong_flag: '❌',
+            exploded: '💥',
+            safe_hint: '🟦',
+            guess_hint: '🟨',
+            flag_counter: '🚩',
+            clock: '🕑',
+            trophy: '🏆',
+            horizontal_line: '━',
+            vertical_line: '┃',
+            waiting_face: "🫥",
+            win_face: "🥳",
+            lose_face: "😵",
+            chill_faces: EMOJI_CHILL_FACES,
+        }
+    }
+
+    fn unicode() -> BoardGlyphs {
+        BoardGlyphs {
+            glyph_set: GlyphSet::Unicode,
+            cell_width: 1,
+            unchecked: '░',
+            empty: '∙',
+            flag: '⚑',
+            mine: '✱',
+            wrong_flag: '✗',
+            exploded: '✸',
+            safe_hint: '✓',
+            guess_hint: '◊',
+            flag_counter: '⚑',
+            clock: '⧗',
+            trophy: '♛',
+            horizontal_line: '━',
+            vertical_line: '┃',
+            waiting_face: "..",
+            win_face: "B)",
+            lose_face: "X(",
+            chill_faces: TEXT_CHILL_FACES,
+        }
+    }
+
+    fn ascii() -> BoardGlyphs {
+        BoardGlyphs {
+            glyph_set: GlyphSet::Ascii,
+            cell_width: 1,
+            unchecked: '#',
+            empty: '.',
+            flag: 'F',
+            mine: '*',
+            wrong_flag: 'X',
+            exploded: '@',
+            safe_hint: '+',
+            guess_hint: '?',
+            flag_counter: 'F',
+            clock: 'T',
+            trophy: 'B',
+            horizontal_line: '-',
+            vertical_line: '|',
+            waiting_face: "..",
+            win_face: "B)",
+            lose_face: "X(",
+            chill_faces: TEXT_CHILL_FACES,
+        }
+    }
+
+    pub fn get_chill_face(&self, chill_factor: ZeroToEight) -> &'static str {
+        self.chill_faces[chill_factor as usize]
+    }
+
+    // A digit or sign in the width of a cell. Full width forms fill the two columns of an emoji cell.
+    pub fn get_cell_char(&self, c: char) -> char {
+        match self.cell_width {
+            2 => char::from_u32(c as u32 + 0xFEE0).unwrap_or(c),
+            _ => c,
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use crate::game_type::GameType;
+use crate::game_view::GameView;
+use crate::minesweeper_descriptor::MINESWEEPER_ID;
+use crate::minesweeper_variant::MinesweeperVariant;
+use crossterm::Result;
+use game_actions::click_action::ClickAction;
+use tui::screen::dimension::Dimension;
+use tui::screen::point::Point;
+use tui::screen::style::Style;
+use tui::screen::text;
+use tui::screen::theme::Theme;
+use tui::screen::window::checkbox::CheckboxComponent;
+use tui::screen::window::component::Component;
+use tui::screen::window::key_action::KeyAction;
+use tui::screen::window::mouse_action::MouseAction;
+use tui::screen::window::number_input::NumberInputComponent;
+use tui::screen::window::update_element::UpdateElement;
+use uuid::Uuid;
+
+const DIALOG_WIDTH: i32 = 26;
+const LABELS: [&str; 3] = ["Width", "Height", "Mines"];
+const INPUT_X: i32 = 8;
+const MAX_VALUE: i32 = 999;
+const NO_GUESS_ROW: usize = LABELS.len();
+const START_ROW: i32 = 5;
+const MESSAGE_ROW: i32 = 6;
+
+// Lets the player pick the width, height and mine count of a custom game, and whether the
+// board has to be solvable without guessing. Left clicking [-] or [+] changes a value by 1 and
+// right clicking by 10. With the keyboard, Up and Down pick a row, Left and Right or digits
+// change its value, Space ticks No guessing and Enter starts the game.
+pub struct CustomGameDialog {
+    id: Uuid,
+    inputs: [NumberInputComponent<ClickAction>; 3],
+    no_guess: CheckboxComponent<ClickAction>,
+    // Row picked with the keyboard, where NO_GUESS_ROW + 1 is the Start row.
+    selected: usize,
+    changed: bool,
+    theme: Theme,
+}
+
+impl CustomGameDialog {
+    pub fn new(game_type: GameType, no_guess: bool) -> Self {
+        let (width, height, mines) = GameView::get_board_settings(game_type);
+        CustomGameDialog {
+            id: Uuid::new_v4(),
+            inputs: [width, height, mines].map(|value| NumberInputComponent::new(value, 1, MAX_VALUE, None)),
+            no_guess: CheckboxComponent::new(Box::from("No guessing"), no_guess, None),
+            selected: 0,
+            changed: true,
+            theme: Theme::default(),
+        }
+    }
+
+    fn get_game_type(&self) -> GameType {
+        GameType::Custom {
+            width: self.inputs[0].get_value(),
+            height: self.inputs[1].get_value(),
+            mines: self.inputs[2].get_value(),
+            no_guess: self.no_guess.is_checked(),
+        }
+    }
+
+    fn push_text(updates: &mut Vec<UpdateElement>, point: Point, text: &str, style: Style) {
+        updates.append(&mut text::layout(text, point, text::get_width(text), style));
+    }
+
+    fn get_row_style(&self, row: usize) -> Style {
+        match self.selected == row {
+            true => self.theme.selected,
+            false => Style::default(),
+        }
+    }
+
+    fn commit_inputs(&mut self) {
+        for input in self.inputs.iter_mut() {
+            input.commit();
+        }
+    }
+
+    fn start(&self) -> Vec<ClickAction> {
+        match GameView::validate_game_type(self.get_game_type()) {
+            Ok(_) => vec![ClickAction::Open(MINESWEEPER_ID, MinesweeperVariant::Play(self.get_game_type()).into()), ClickAction::Close(self.id)],
+            Err(_) => vec![],
+        }
+    }
+}
+
+impl Component<ClickAction> for CustomGameDialog {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        (DIALOG_WIDTH, MESSAGE_ROW + 1).into()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        let mut updates = vec![];
+        for (row, label) in LABELS.iter().enumerate() {
+            let row_style = self.get_row_style(row);
+            CustomGameDialog::push_text(&mut updates, (0, row as i32).into(), &text::fit(label, DIALOG_WIDTH), row_style);
+            for update in self.inputs[row].get_state()? {
+                updates.push(UpdateElement {
+                    point: update.point + (INPUT_X, row as i32).into(),
+                    style: update.style.over(row_style),
+                    ..update
+                });
+            }
+        }
+        let no_guess_style = self.get_row_style(NO_GUESS_ROW);
+        CustomGameDialog::push_text(&mut updates, (0, NO_GUESS_ROW as i32).into(), &" ".repeat(DIALOG_WIDTH as usize), no_guess_style);
+        for update in self.no_guess.get_state()? {
+            updates.push(UpdateElement {
+                point: update.point + (INPUT_X, NO_GUESS_ROW as i32).into(),
+                style: update.style.over(no_guess_style),
+                ..update
+            });
+        }
+        let start = format!("{:^w$}", "[ Start ]", w = DIALOG_WIDTH as usize);
+        CustomGameDialog::push_text(&mut updates, (0, START_ROW).into(), &start, self.get_row_style(NO_GUESS_ROW + 1));
+
+        let (message, style) = match GameView::validate_game_type(self.get_game_type()) {
+            Ok(_) => (String::new(), Style::default()),
+            Err(e) => (e.to_string(), self.theme.error),
+        };
+        CustomGameDialog::push_text(&mut updates, (0, MESSAGE_ROW).into(), &text::fit(&message, DIALOG_WIDTH), style);
+        self.changed = false;
+        Ok(updates)
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        if self.changed {
+            self.get_state()
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    fn handle_click(&mut self, click: MouseAction) -> Result<Vec<ClickAction>> {
+        // The mouse moving over the dialog is not a click.
+        if matches!(click, MouseAction::Left(_) | MouseAction::Right(_)) {
+            self.commit_inputs();
+        }
+        let point = click.to_point();
+        if (0..LABELS.len() as i32).contains(&point.y) {
+            let row = point.y as usize;
+            let input_point = point - (INPUT_X, point.y).into();
+            let input_click = match click {
+                MouseAction::Left(_) => MouseAction::Left(input_point),
+                MouseAction::Right(_) => MouseAction::Right(input_point),
+                _ => return Ok(vec![]),
+            };
+            self.inputs[row].handle_click(input_click)?;
+            self.changed = true;
+        } else if point.y == NO_GUESS_ROW as i32 {
+            self.no_guess.handle_click(click)?;
+            self.changed = true;
+        } else if point.y == START_ROW {
+            if let MouseAction::Left(_) = click {
+                return Ok(self.start());
+            }
+        }
+        Ok(vec![])
+    }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<ClickAction>>> {
+        match key {
+            KeyAction::Up => {
+                self.commit_inputs();
+                self.selected = (self.selected + NO_GUESS_ROW + 1) % (NO_GUESS_ROW + 2);
+            }
+            KeyAction::Down => {
+                self.commit_inputs();
+                self.selected = (self.selected + 1) % (NO_GUESS_ROW + 2);
+            }
+            KeyAction::Enter => {
+                self.commit_inputs();
+                self.changed = true;
+                return Ok(Some(self.start()));
+            }
+            KeyAction::Escape => return Ok(Some(vec![ClickAction::Close(self.id)])),
+            _ if self.selected < LABELS.len() => {
+                if self.inputs[self.selected].handle_key(key)?.is_none() {
+                    return Ok(None);
+                }
+            }
+            KeyAction::Char(' ') if self.selected == NO_GUESS_ROW => {
+                self.no_guess.handle_key(key)?;
+            }
+            _ => return Ok(None),
+        }
+        self.changed = true;
+        Ok(Some(vec![]))
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+        for input in self.inputs.iter_mut() {
+            input.set_theme(theme);
+        }
+        self.no_guess.set_theme(theme);
+        self.changed = true;
+    }
+}
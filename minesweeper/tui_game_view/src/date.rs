@@ -0,0 +1,58 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+// YYYY-MM-DD in UTC.
+pub fn format_date(date: u64) -> String {
+    // Days since 1970-01-01 to a civil date, from Howard Hinnant's chrono-compatible algorithms.
+    let z = (date / 86400) as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// Seconds with milliseconds, such as 42.075s.
+pub fn format_time(millis: u64) -> String {
+    format!("{}.{:03}s", millis / 1000, millis % 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_are_formatted_in_utc() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(1709251200 - 1), "2024-02-29");
+        assert_eq!(format_date(1709251200), "2024-03-01");
+    }
+
+    #[test]
+    fn leap_days_are_kept() {
+        assert_eq!(format_date(951782400), "2000-02-29");
+        assert_eq!(format_date(1709164800), "2024-02-29");
+    }
+
+    #[test]
+    fn last_second_of_a_year_is_still_in_it() {
+        assert_eq!(format_date(946684800 - 1), "1999-12-31");
+        assert_eq!(format_date(946684800), "2000-01-01");
+        assert_eq!(format_date(1704067200 - 1), "2023-12-31");
+        assert_eq!(format_date(1704067200), "2024-01-01");
+    }
+
+    #[test]
+    fn times_keep_their_milliseconds() {
+        assert_eq!(format_time(42075), "42.075s");
+        assert_eq!(format_time(5), "0.005s");
+    }
+}
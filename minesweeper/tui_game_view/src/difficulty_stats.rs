@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use crate::best_time::BestTime;
+
+const MAX_BEST_TIMES: usize = 10;
+
+// Everything recorded about the games played on one difficulty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DifficultyStats {
+    pub played: u64,
+    pub wins: u64,
+    // Wins in a row up to the last game.
+    pub current_streak: u64,
+    pub longest_streak: u64,
+    // The time in milliseconds of every win played without undo, for the average and median.
+    // Version 1 statistics kept whole seconds here.
+    #[serde(alias = "win_times")]
+    pub win_times_millis: Vec<u64>,
+    // The fastest wins, fastest first.
+    pub best_times: Vec<BestTime>,
+}
+
+impl DifficultyStats {
+    pub fn get_losses(&self) -> u64 {
+        self.played - self.wins
+    }
+
+    // As a percentage, None before the first game.
+    pub fn get_win_rate(&self) -> Option<u64> {
+        match self.played {
+            0 => None,
+            played => Some((self.wins * 100 + played / 2) / played),
+        }
+    }
+
+    pub fn get_average_win_time(&self) -> Option<u64> {
+        match self.win_times_millis.len() as u64 {
+            0 => None,
+            count => Some((self.win_times_millis.iter().sum::<u64>() + count / 2) / count),
+        }
+    }
+
+    pub fn get_median_win_time(&self) -> Option<u64> {
+        let mut win_times = self.win_times_millis.clone();
+        win_times.sort_unstable();
+        let middle = win_times.len() / 2;
+        match win_times.len() {
+            0 => None,
+            count if count % 2 == 0 => Some((win_times[middle - 1] + win_times[middle]).div_ceil(2)),
+            _ => Some(win_times[middle]),
+        }
+    }
+
+    pub fn get_best_time(&self) -> Option<u64> {
+        self.best_times.first().map(|best_time| best_time.time_millis)
+    }
+
+    // A win without a time still counts towards the streak, but not towards the times.
+    pub fn record_win(&mut self, time_millis: Option<u64>, date: u64) {
+        self.played += 1;
+        self.wins += 1;
+        self.current_streak += 1;
+        self.longest_streak = self.longest_streak.max(self.current_streak);
+        if let Some(time_millis) = time_millis {
+            self.win_times_millis.push(time_millis);
+            self.add_best_time(BestTime { time_millis, date: Some(date) });
+        }
+    }
+
+    pub fn record_loss(&mut self) {
+        self.played += 1;
+        self.current_streak = 0;
+    }
+
+    pub fn add_best_time(&mut self, best_time: BestTime) {
+        // Equal times keep the one that was set first in front.
+        let idx = self.best_times.partition_point(|b| b.time_millis <= best_time.time_millis);
+        self.best_times.insert(idx, best_time);
+        self.best_times.truncate(MAX_BEST_TIMES);
+    }
+
+    // Version 1 statistics kept whole seconds.
+    pub(crate) fn convert_seconds_to_millis(&mut self) {
+        for time in self.win_times_millis.iter_mut() {
+            *time *= 1000;
+        }
+        for best_time in self.best_times.iter_mut() {
+            best_time.time_millis *= 1000;
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+const NO_GUESS_SUFFIX: &str = " no-guess";
+
+#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum GameType {
+    Small,
+    Medium,
+    Large,
+    // A no-guess custom game keeps its own scores, as it never needs a lucky guess.
+    Custom {
+        width: i32,
+        height: i32,
+        mines: i32,
+        #[serde(default)]
+        no_guess: bool,
+    },
+}
+
+impl GameType {
+    // Small and large are accepted too, as the snake names of the sizes.
+    pub fn from_difficulty(value: &str) -> Result<GameType, String> {
+        match value.to_lowercase().as_str() {
+            "easy" | "small" => Ok(GameType::Small),
+            "medium" => Ok(GameType::Medium),
+            "hard" | "large" => Ok(GameType::Large),
+            _ => Err(format!("Unknown difficulty {}, expected easy, medium or hard", value)),
+        }
+    }
+
+    pub fn get_label(self) -> String {
+        match self {
+            GameType::Small => "Easy".to_string(),
+            GameType::Medium => "Medium".to_string(),
+            GameType::Large => "Hard".to_string(),
+            GameType::Custom { width, height, mines, no_guess: false } => format!("Custom {}x{} ({})", width, height, mines),
+            GameType::Custom { width, height, mines, no_guess: true } => format!("No-guess {}x{} ({})", width, height, mines),
+        }
+    }
+
+    // Such as "Small", "Custom 20x10 30" or "Custom 20x10 30 no-guess".
+    pub fn to_key(self) -> String {
+        match self {
+            GameType::Custom { width, height, mines, no_guess: false } => format!("Custom {}x{} {}", width, height, mines),
+            GameType::Custom { width, height, mines, no_guess: true } => format!("Custom {}x{} {}{}", width, height, mines, NO_GUESS_SUFFIX),
+            _ => format!("{:?}", self),
+        }
+    }
+
+    pub fn from_key(key: &str) -> Result<GameType, String> {
+        let invalid = || format!("Invalid game type {}", key);
+        match key {
+            "Small" => Ok(GameType::Small),
+            "Medium" => Ok(GameType::Medium),
+            "Large" => Ok(GameType::Large),
+            _ => {
+                let settings = key.strip_prefix("Custom ").ok_or_else(invalid)?;
+                let (settings, no_guess) = match settings.strip_suffix(NO_GUESS_SUFFIX) {
+                    Some(settings) => (settings, true),
+                    None => (settings, false),
+                };
+                let (size, mines) = settings.split_once(' ').ok_or_else(invalid)?;
+                let (width, height) = size.split_once('x').ok_or_else(invalid)?;
+                Ok(GameType::Custom {
+                    width: width.parse().map_err(|_| invalid())?,
+                    height: height.parse().map_err(|_| invalid())?,
+                    mines: mines.parse().map_err(|_| invalid())?,
+                    no_guess,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_round_trip() {
+        let game_types = [
+            GameType::Small,
+            GameType::Large,
+            GameType::Custom { width: 20, height: 10, mines: 30, no_guess: false },
+            GameType::Custom { width: 20, height: 10, mines: 30, no_guess: true },
+        ];
+        for game_type in game_types {
+            assert_eq!(GameType::from_key(&game_type.to_key()), Ok(game_type));
+        }
+        assert_eq!(GameType::Custom { width: 20, height: 10, mines: 30, no_guess: true }.to_key(), "Custom 20x10 30 no-guess");
+    }
+
+    #[test]
+    fn no_guess_needs_a_space_before_it() {
+        assert!(GameType::from_key("Custom 20x10 30no-guess").is_err());
+        assert!(GameType::from_key("Custom 20x10no-guess").is_err());
+        assert!(GameType::from_key("Custom 20x10 no-guess").is_err());
+    }
+}
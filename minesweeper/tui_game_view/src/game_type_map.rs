@@ -0,0 +1,17 @@
+use std::collections::HashMap;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::game_type::GameType;
+
+// Serialises maps keyed by GameType with the keys of GameType::to_key, for use with
+// #[serde(with = "crate::game_type_map")]. YAML can not have a Custom game type itself as a key.
+pub fn serialize<S: Serializer, V: Serialize>(map: &HashMap<GameType, V>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(map.iter().map(|(game_type, value)| (game_type.to_key(), value)))
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>, V: Deserialize<'de>>(deserializer: D) -> Result<HashMap<GameType, V>, D::Error> {
+    HashMap::<String, V>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(key, value)| GameType::from_key(&key).map(|game_type| (game_type, value)).map_err(D::Error::custom))
+        .collect()
+}
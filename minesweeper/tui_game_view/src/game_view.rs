@@ -1,99 +1,346 @@
+use crate::board_glyphs::BoardGlyphs;
+use crate::date::now;
+use crate::difficulty_stats::DifficultyStats;
+use crate::game_type::GameType;
+use crate::leaderboard::{Leaderboard, MAX_NAME_LEN};
+use crate::leaderboard_entry::LeaderboardEntry;
 use crate::test_engine::TestEngine;
-use crate::top_score::TopScore;
-use crossterm::{style::Color, Result};
+use crate::minesweeper_stats::MinesweeperStats;
+use crate::replay_file::{ReplayFile, REPLAY_FILE_VERSION};
+use crossterm::{ErrorKind, Result};
 use game_actions::click_action::ClickAction;
 use game_actions::click_action::ClickAction::Refresh;
-use game_actions::game_type::GameType;
+use game_actions::config::minesweeper_config::MinesweeperConfig;
+use game_actions::data_store::DataStore;
+use game_actions::game_context::GameContext;
 use minesweeper_engine::can_be_engine::CanBeEngine;
 use minesweeper_engine::cell::Cell;
 use minesweeper_engine::cell_state::CellState;
 use minesweeper_engine::cell_state::CellState::{Bomb, Checked, Flagged, Unchecked};
 use minesweeper_engine::complete_state::CompleteState;
 use minesweeper_engine::engine::Engine;
+use minesweeper_engine::game_state::GameState;
 use minesweeper_engine::game_state::GameState::{Complete, Initialised, Playing};
 use minesweeper_engine::game_stats::GameStats;
+use minesweeper_engine::generation_mode::{GenerationMode, NoGuessSettings};
 use minesweeper_engine::move_type::MoveType;
+use minesweeper_engine::replay_player::ReplayPlayer;
 use minesweeper_engine::zero_to_eight::ZeroToEight;
-use minesweeper_engine::zero_to_eight::ZeroToEight::{
-    Eight, Five, Four, One, Seven, Six, Three, Two, Zero,
-};
+use minesweeper_engine::zero_to_eight::ZeroToEight::Zero;
 use std::collections::{HashMap, HashSet};
-use std::env::current_exe;
-use std::fs::{write, File};
+use std::fs::File;
 use std::path::Path;
+use std::time::{Duration, Instant};
 use tui::screen::dimension::Dimension;
 use tui::screen::point::Point;
+use tui::screen::style::Style;
+use tui::screen::theme::Theme;
 use tui::screen::window::component::Component;
+use tui::screen::window::key_action::KeyAction;
 use tui::screen::window::mouse_action::MouseAction;
+use tui::screen::window::text_input::TextInputComponent;
 use tui::screen::window::update_element::UpdateElement;
 use uuid::Uuid;
+use log::error;
 
 const VISUAL_TEST: bool = false;
+pub const MIN_WIDTH: i32 = 11;
+pub const MAX_WIDTH: i32 = 60;
+pub const MIN_HEIGHT: i32 = 4;
+pub const MAX_HEIGHT: i32 = 40;
+pub const LAST_REPLAY_FILE_NAME: &str = "minesweeper_last_replay.yaml";
+// The clock shows whole seconds, so a few ticks a second keep it close to the real time.
+const CLOCK_TICK: Duration = Duration::from_millis(200);
+const REPLAY_TICK: Duration = Duration::from_millis(50);
+const NAME_LABEL: &str = "Name: ";
+// The speeds the faster and slower keys step through, as multiples of real time.
+const REPLAY_SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
 
 pub struct GameView {
     id: Uuid,
     engine: Box<dyn CanBeEngine>,
     engine_size: Dimension,
     game_type: GameType,
-    top_score_data: TopScore,
+    // Best time of this difficulty, known once a game is complete.
+    best_time: Option<u64>,
+    // Moves after the game is complete report it as complete again, but it is only saved once.
+    saved: bool,
+    // The statistics of this difficulty from before the game was saved, put back when its loss is undone.
+    stats_before_save: Option<DifficultyStats>,
+    // The player is told once when a no-guess board could not be found.
+    no_guess_fallback_shown: bool,
     retry_button_location: Vec<Point>,
+    // Asks for a name in place of the retry button when a win makes the leaderboard.
+    name_input: Option<TextInputComponent<ClickAction>>,
+    // The win waiting for its name.
+    pending_entry: Option<LeaderboardEntry>,
     chill_factor: ZeroToEight,
-}
-
-fn convert_to_wide_char(c: char) -> char {
-    char::from_u32(c as u32 + 0xFEE0).unwrap()
+    // The hinted cell and whether it is certainly safe rather than just the least risky guess.
+    hint_cell: Option<(Cell, bool)>,
+    // The cell picked with the keyboard. Hidden until a key is used.
+    cursor: Option<Cell>,
+    // Cells the hint or cursor moved on or off, which have to be drawn again from the board state.
+    stale_cells: HashSet<Cell>,
+    is_replay: bool,
+    last_tick: Instant,
+    data_store: DataStore,
+    config: MinesweeperConfig,
+    glyphs: BoardGlyphs,
+    theme: Theme,
 }
 
 impl GameView {
     pub fn new(game_type: GameType) -> GameView {
+        GameView::new_with_context(game_type, &GameContext::default())
+    }
+
+    pub fn new_with_context(game_type: GameType, context: &GameContext) -> GameView {
+        let (width, height, mines) = GameView::get_board_settings(game_type);
+        let mut engine = match context.seed {
+            Some(seed) => Engine::new_with_seed(width, height, mines, seed),
+            None => Engine::new(width, height, mines),
+        };
+        // A custom game carries its own choice, the difficulties follow the config file.
+        let no_guess = match game_type {
+            GameType::Custom { no_guess, .. } => no_guess,
+            _ => context.config.minesweeper.no_guess,
+        };
+        if no_guess {
+            engine.set_generation_mode(GenerationMode::NoGuess(NoGuessSettings::default()));
+        }
         let engine: Box<dyn CanBeEngine> = match VISUAL_TEST {
             true => Box::from(TestEngine::new()),
-            false => Box::from(match game_type {
-                GameType::Small => Engine::new(11, 8, 12),
-                GameType::Medium => Engine::new(19, 14, 45),
-                GameType::Large => Engine::new(25, 20, 100),
-            }),
+            false => Box::from(engine),
         };
+        GameView::new_with_engine(game_type, engine, context, false)
+    }
+
+    pub fn get_board_settings(game_type: GameType) -> (i32, i32, i32) {
+        match game_type {
+            GameType::Small => (11, 8, 12),
+            GameType::Medium => (19, 14, 45),
+            GameType::Large => (25, 20, 100),
+            GameType::Custom { width, height, mines, .. } => (width, height, mines),
+        }
+    }
+
+    // On top of what the engine allows, the board has to be wide enough for the stats line
+    // and small enough to fit in a terminal.
+    pub fn validate_game_type(game_type: GameType) -> Result<()> {
+        let (width, height, mines) = GameView::get_board_settings(game_type);
+        if !(MIN_WIDTH..=MAX_WIDTH).contains(&width) {
+            Err(ErrorKind::other(format!("Width must be {}-{}", MIN_WIDTH, MAX_WIDTH)))?;
+        }
+        if !(MIN_HEIGHT..=MAX_HEIGHT).contains(&height) {
+            Err(ErrorKind::other(format!("Height must be {}-{}", MIN_HEIGHT, MAX_HEIGHT)))?;
+        }
+        Engine::validate_settings(width, height, mines)
+    }
+
+    // Plays a saved game back. The view ignores moves and does not record scores.
+    pub fn new_replay(replay_file: ReplayFile, context: &GameContext, speed: f64) -> Result<GameView> {
+        if replay_file.version != REPLAY_FILE_VERSION {
+            Err(ErrorKind::other(format!(
+                "Replay file version {} is not supported, expected version {}",
+                replay_file.version, REPLAY_FILE_VERSION
+            )))?;
+        }
+        let player = ReplayPlayer::new(replay_file.replay, speed)?;
+        Ok(GameView::new_with_engine(replay_file.game_type, Box::from(player), context, true))
+    }
+
+    pub fn load_replay_file(path: &Path) -> Result<ReplayFile> {
+        let file = File::open(path)?;
+        serde_yaml::from_reader(file).map_err(ErrorKind::other)
+    }
+
+    pub fn from_last_replay(context: &GameContext, speed: f64) -> Result<GameView> {
+        match context.get_data_store().load(LAST_REPLAY_FILE_NAME)? {
+            Some(replay_file) => GameView::new_replay(replay_file, context, speed),
+            None => Err(ErrorKind::other("There is no replay yet, finish a game first")),
+        }
+    }
+
+    fn new_with_engine(game_type: GameType, engine: Box<dyn CanBeEngine>, context: &GameContext, is_replay: bool) -> GameView {
         let engine_size: Dimension = engine.get_size().into();
         GameView {
             id: Uuid::new_v4(),
             engine,
             engine_size,
             game_type,
-            top_score_data: TopScore {
-                scores: HashMap::new(),
-            },
+            best_time: None,
+            saved: false,
+            stats_before_save: None,
+            no_guess_fallback_shown: false,
             retry_button_location: vec![],
+            name_input: None,
+            pending_entry: None,
             chill_factor: Zero,
+            hint_cell: None,
+            cursor: None,
+            stale_cells: HashSet::new(),
+            is_replay,
+            last_tick: Instant::now(),
+            data_store: context.get_data_store(),
+            config: context.config.minesweeper.clone(),
+            glyphs: BoardGlyphs::new(context.config.minesweeper.glyph_set, &context.config.minesweeper.glyphs),
+            theme: Theme::default(),
+        }
+    }
+
+    fn save_replay(&self) -> Result<()> {
+        match self.engine.get_replay() {
+            Some(replay) => self.data_store.save(LAST_REPLAY_FILE_NAME, &ReplayFile {
+                version: REPLAY_FILE_VERSION,
+                game_type: self.game_type,
+                replay,
+            }),
+            None => Ok(()),
         }
     }
 
     fn reset(&mut self) {
         self.engine = self.engine.make_clone();
+        self.hint_cell = None;
+        self.stale_cells.clear();
+        self.saved = false;
+        self.stats_before_save = None;
+        self.no_guess_fallback_shown = false;
+        self.name_input = None;
+        self.pending_entry = None;
+    }
+
+    // The face is two columns wide in every glyph set.
+    fn get_face_point(&self) -> Point {
+        ((self.get_size().width / 2) - 1, 0).into()
+    }
+
+    // An icon and three digits, such as the flags left, as x offsets and characters. No value
+    // shows dashes.
+    fn get_counter_chars(&self, icon: char, value: Option<i64>) -> Vec<(i32, char)> {
+        let digits = match value {
+            Some(value) => format!("{:03}", value.min(999)),
+            None => "---".to_string(),
+        };
+        let digits = digits.chars().enumerate().map(|(i, c)| ((i as i32 + 1) * self.glyphs.cell_width, self.glyphs.get_cell_char(c)));
+        std::iter::once((0, icon)).chain(digits).collect()
+    }
+
+    fn get_counter_width(&self) -> i32 {
+        4 * self.glyphs.cell_width
+    }
+
+    // Steps to the next or previous of REPLAY_SPEEDS. Live games have no speed to change.
+    fn change_replay_speed(&mut self, steps: i32) {
+        if let Some(speed) = self.engine.get_speed() {
+            let current = REPLAY_SPEEDS.iter().position(|s| *s >= speed).unwrap_or(REPLAY_SPEEDS.len() - 1);
+            let next = (current as i32 + steps).clamp(0, REPLAY_SPEEDS.len() as i32 - 1);
+            self.engine.set_speed(REPLAY_SPEEDS[next as usize]);
+        }
+    }
+
+    fn switch_glyph_set(&mut self) {
+        self.glyphs = BoardGlyphs::new(self.glyphs.glyph_set.next(), &self.config.glyphs);
+        self.stale_cells.clear();
+    }
+
+    // Picks one certainly safe cell, or failing that the cell least likely to be a mine.
+    fn show_hint(&mut self) -> Result<()> {
+        let hint = self.engine.get_hint()?;
+        let by_position = |c: &&Cell| (c.y, c.x);
+        self.clear_hint();
+        self.hint_cell = match hint.safe_cells.iter().min_by_key(by_position) {
+            Some(cell) => Some((*cell, true)),
+            None => hint
+                .mine_probabilities
+                .iter()
+                .min_by(|(c1, p1), (c2, p2)| {
+                    p1.total_cmp(p2).then_with(|| by_position(c1).cmp(&by_position(c2)))
+                })
+                .map(|(cell, _)| (*cell, false)),
+        };
+        Ok(())
+    }
+
+    fn clear_hint(&mut self) {
+        if let Some((cell, _)) = self.hint_cell.take() {
+            self.stale_cells.insert(cell);
+        }
+    }
+
+    // Shows the cursor in the middle of the board the first time, otherwise moves it by the offset.
+    fn move_cursor(&mut self, x_offset: i32, y_offset: i32) -> Result<()> {
+        let cursor = match self.cursor {
+            None => Cell {
+                x: self.engine_size.width / 2,
+                y: self.engine_size.height / 2,
+            },
+            Some(cursor) => {
+                self.stale_cells.insert(cursor);
+                Cell {
+                    x: (cursor.x + x_offset).clamp(0, self.engine_size.width - 1),
+                    y: (cursor.y + y_offset).clamp(0, self.engine_size.height - 1),
+                }
+            }
+        };
+        self.stale_cells.insert(cursor);
+        self.cursor = Some(cursor);
+        self.chill_factor = self.engine.get_chill_factor(cursor)?;
+        Ok(())
+    }
+
+    // The cursor is drawn over the style of the cell it is on.
+    fn get_cell_style(&self, cell: Cell, style: Style) -> Style {
+        match self.cursor == Some(cell) {
+            true => self.config.cursor_color.map(Style::from_bg).unwrap_or(self.theme.selected).over(style),
+            false => style,
+        }
+    }
+
+    // Colours set in the config file win over the palette of the theme.
+    fn get_number_style(&self, number: usize) -> Style {
+        match &self.config.number_colors {
+            Some(colors) => Style::from_fg(colors[number - 1]),
+            None => self.theme.palette[number - 1],
+        }
+    }
+
+    fn append_overlay_updates(&mut self, prior_updates: &mut Vec<UpdateElement>) -> Result<()> {
+        if !self.stale_cells.is_empty() {
+            let board_state = self.engine.get_board_state();
+            let cell_updates = self
+                .stale_cells
+                .drain()
+                .filter_map(|cell| board_state.get(&cell).map(|cell_state| (cell, *cell_state)))
+                .collect();
+            self.append_updates_from_engine(&cell_updates, prior_updates)?;
+        }
+        if let Some((cell, certain)) = self.hint_cell {
+            prior_updates.push(UpdateElement {
+                point: (cell.x * self.glyphs.cell_width, cell.y + 2).into(),
+                value: if certain { self.glyphs.safe_hint } else { self.glyphs.guess_hint }.to_string(),
+                style: self.get_cell_style(cell, Style::default()),
+            });
+        }
+        Ok(())
     }
 
     fn convert_engine_size_to_size(&self) -> Dimension {
-        let engine_size = self.engine_size.clone();
+        let engine_size = self.engine_size;
         let y_offset = if let Complete(_) = self.engine.get_game_stats().game_state {
             4
         } else {
             2
         };
-        (engine_size.width * 2, engine_size.height + y_offset).into()
+        (engine_size.width * self.glyphs.cell_width, engine_size.height + y_offset).into()
     }
 
-    fn push_stat_char(
-        updates: &mut Vec<UpdateElement>,
-        stat_line_points: &mut HashSet<Point>,
-        point: Point,
-        value: char,
-    ) {
+    fn push_stat_char(updates: &mut Vec<UpdateElement>, point: Point, value: char) {
         updates.push(UpdateElement {
             point,
-            value,
-            fg: None,
+            value: value.to_string(),
+            style: Style::default(),
         });
-        stat_line_points.remove(&point);
     }
 
     pub fn get_stats_board_updates(
@@ -102,69 +349,45 @@ impl GameView {
         prior_updates: &mut Vec<UpdateElement>,
     ) -> Result<()> {
         let size = self.get_size();
-        let mut stat_line_points = HashSet::new();
         for x in 0..size.width {
+            // Clear the stats line before drawing it.
+            GameView::push_stat_char(prior_updates, (x, 0).into(), ' ');
             // draw separator
-            prior_updates.push(UpdateElement {
-                point: (x, 1).into(),
-                value: '━',
-                fg: None,
-            });
-            stat_line_points.insert((x, 0).into());
+            GameView::push_stat_char(prior_updates, (x, 1).into(), self.glyphs.horizontal_line);
+        }
+        // A replay shows its speed at the right end of the separator.
+        if let Some(speed) = self.engine.get_speed() {
+            let label = format!(" {}x ", speed);
+            let label_x = size.width - label.len() as i32 - 1;
+            for (i, char) in label.chars().enumerate() {
+                GameView::push_stat_char(prior_updates, (label_x + i as i32, 1).into(), char);
+            }
         }
 
-        let emoji = match game_stats.game_state {
-            Initialised => '🫥',
-            Playing => GameView::get_emoji_from_chill_factor(self.chill_factor),
+        let face = match game_stats.game_state {
+            Initialised => self.glyphs.waiting_face,
+            Playing => self.glyphs.get_chill_face(self.chill_factor),
             Complete(result) => match result {
-                CompleteState::Win => '🥳',
-                CompleteState::Lose => '😵',
+                CompleteState::Win => self.glyphs.win_face,
+                CompleteState::Lose => self.glyphs.lose_face,
             },
         };
-        let emoji_point = ((size.width / 2) - 1, 0).into();
-        GameView::push_stat_char(prior_updates, &mut stat_line_points, emoji_point, emoji);
-
-        // draw flag count
-        let flag_point = (((emoji_point.x / 2) - 1 - 4), emoji_point.y).into();
-        let mut flags = game_stats.flags_remaining;
-        if flags > 999 {
-            flags = 999;
-        }
-        let flag_string = format!("{:03}", flags);
-        GameView::push_stat_char(prior_updates, &mut stat_line_points, flag_point, '🚩');
-        for (i, char) in flag_string.chars().enumerate() {
-            GameView::push_stat_char(
-                prior_updates,
-                &mut stat_line_points,
-                flag_point + (((i + 1) as i32) * 2, 0).into(),
-                convert_to_wide_char(char),
-            );
-        }
-
-        // draw clock
-        let clock_point = ((((emoji_point.x / 2) - 1) + emoji_point.x), emoji_point.y).into();
-        let mut seconds = game_stats.game_run_time;
-        if seconds > 999 {
-            seconds = 999;
-        }
-        let seconds_string = format!("{:03}", seconds);
-        GameView::push_stat_char(prior_updates, &mut stat_line_points, clock_point, '🕑');
-
-        for (i, char) in seconds_string.chars().enumerate() {
-            GameView::push_stat_char(
-                prior_updates,
-                &mut stat_line_points,
-                clock_point + (((i + 1) as i32) * 2, 0).into(),
-                convert_to_wide_char(char),
-            );
-        }
-
-        for left_over_point in stat_line_points.iter() {
-            prior_updates.push(UpdateElement {
-                point: *left_over_point,
-                value: char::default(),
-                fg: None,
-            });
+        let face_point = self.get_face_point();
+        for (i, char) in face.chars().enumerate() {
+            GameView::push_stat_char(prior_updates, face_point + (i as i32, 0).into(), char);
+        }
+
+        // The flag count in the middle of the left half, the clock in the middle of the right half.
+        let counter_width = self.get_counter_width();
+        let flag_point: Point = ((face_point.x - counter_width) / 2, 0).into();
+        for (x, char) in self.get_counter_chars(self.glyphs.flag_counter, Some(game_stats.flags_remaining as i64)) {
+            GameView::push_stat_char(prior_updates, flag_point + (x, 0).into(), char);
+        }
+        let right_x = face_point.x + 2;
+        let clock_point: Point = (right_x + (size.width - right_x - counter_width) / 2, 0).into();
+        let seconds = (game_stats.game_run_time_millis / 1000) as i64;
+        for (x, char) in self.get_counter_chars(self.glyphs.clock, Some(seconds)) {
+            GameView::push_stat_char(prior_updates, clock_point + (x, 0).into(), char);
         }
 
         Ok(())
@@ -176,169 +399,180 @@ impl GameView {
         prior_updates: &mut Vec<UpdateElement>,
     ) -> Result<()> {
         for (cell, cell_state) in game_updates.iter() {
-            let (value, fg) = match cell_state {
-                Unchecked => ('🟩', Color::White),
-                Checked(adjacent_bombs) => match adjacent_bombs {
-                    Zero => ('🟫', Color::White),
-                    One => ('１', Color::White),
-                    Two => ('２', Color::Cyan),
-                    Three => ('３', Color::Green),
-                    Four => ('４', Color::Yellow),
-                    Five => ('５', Color::DarkYellow),
-                    Six => ('６', Color::DarkMagenta),
-                    Seven => ('７', Color::Red),
-                    Eight => ('８', Color::DarkRed),
-                },
-                Flagged => ('🚩', Color::White),
-                Bomb => ('💣', Color::White),
-                CellState::Cross => ('❌', Color::White),
-                CellState::Exploded => ('💥', Color::White),
+            let glyphs = &self.glyphs;
+            let (value, style) = match cell_state {
+                Unchecked => (glyphs.unchecked, Style::default()),
+                Checked(Zero) => (glyphs.empty, Style::default()),
+                Checked(adjacent_bombs) => {
+                    let number = *adjacent_bombs as usize;
+                    (glyphs.get_cell_char(char::from_digit(number as u32, 10).unwrap()), self.get_number_style(number))
+                }
+                Flagged => (glyphs.flag, Style::default()),
+                Bomb => (glyphs.mine, Style::default()),
+                CellState::Cross => (glyphs.wrong_flag, Style::default()),
+                CellState::Exploded => (glyphs.exploded, Style::default()),
             };
 
             prior_updates.push(UpdateElement {
-                point: (cell.x * 2, cell.y + 2).into(),
-                value,
-                fg: Some(fg),
-            });
-
-            prior_updates.push(UpdateElement {
-                point: (cell.x * 2 + 1, cell.y + 2).into(),
-                value: char::default(),
-                fg: None,
+                point: (cell.x * self.glyphs.cell_width, cell.y + 2).into(),
+                value: value.to_string(),
+                style: self.get_cell_style(*cell, style),
             });
         }
         Ok(())
     }
 
-    fn append_complete_menu_updates(
-        &mut self,
-        game_stats: GameStats,
-        updates: &mut Vec<UpdateElement>,
-    ) -> Result<()> {
+    fn append_complete_menu_updates(&mut self, updates: &mut Vec<UpdateElement>) -> Result<()> {
         let size = self.get_size();
         for x in 0..size.width {
             // draw separator
             updates.push(UpdateElement {
                 point: (x, size.height - 2).into(),
-                value: '━',
-                fg: None,
+                value: self.glyphs.horizontal_line.to_string(),
+                style: Style::default(),
             });
         }
-        let halfway_point = ((size.width / 2) - 1, size.height - 1).into();
-        updates.push(UpdateElement {
-            point: halfway_point,
-            value: '┃',
-            fg: None,
-        });
-        let trophy_point = (((halfway_point.x / 2) - 1 - 4), halfway_point.y).into();
-        updates.push(UpdateElement {
-            point: trophy_point,
-            value: '🏆',
-            fg: None,
-        });
-
-        let mut score = match self.top_score_data.scores.get(&self.game_type) {
-            None => {
-                if let Complete(res) = game_stats.game_state {
-                    if res == CompleteState::Win {
-                        game_stats.game_run_time
-                    } else {
-                        0
-                    }
-                } else {
-                    0
-                }
+        // Clear the row, as it switches between the name entry and the retry button.
+        for x in 0..size.width {
+            updates.push(UpdateElement {
+                point: (x, size.height - 1).into(),
+                value: ' '.to_string(),
+                style: Style::default(),
+            });
+        }
+        self.retry_button_location.clear();
+        if let Some(name_input) = self.name_input.as_mut() {
+            for (i, char) in NAME_LABEL.chars().enumerate() {
+                updates.push(UpdateElement {
+                    point: (i as i32, size.height - 1).into(),
+                    value: char.to_string(),
+                    style: Style::default(),
+                });
             }
-            Some(result) => {
-                let owned_result = result.to_owned();
-                if owned_result != u64::MAX {
-                    owned_result
-                } else {
-                    0
-                }
+            for update in Component::<ClickAction>::get_state(name_input)? {
+                updates.push(UpdateElement {
+                    point: update.point + (NAME_LABEL.len() as i32, size.height - 1).into(),
+                    ..update
+                });
             }
-        };
-        if score > 999 {
-            score = 999;
+            return Ok(());
         }
-        let score_string = format!("{:03}", score);
-        for (i, char) in score_string.chars().enumerate() {
+        // The best time left of the line under the face, the retry button right of it.
+        let row = size.height - 1;
+        let line_x = self.get_face_point().x;
+        updates.push(UpdateElement {
+            point: (line_x, row).into(),
+            value: self.glyphs.vertical_line.to_string(),
+            style: Style::default(),
+        });
+        // Dashes until this difficulty has been won.
+        let best_time = self.best_time.map(|best_time| (best_time / 1000) as i64);
+        let trophy_x = (line_x - self.get_counter_width()) / 2;
+        for (x, char) in self.get_counter_chars(self.glyphs.trophy, best_time) {
             updates.push(UpdateElement {
-                point: trophy_point + (((i + 1) as i32) * 2, 0).into(),
-                value: convert_to_wide_char(char),
-                fg: None,
+                point: (trophy_x + x, row).into(),
+                value: char.to_string(),
+                style: Style::default(),
             });
         }
 
-        let retry_point: Point = (
-            (((halfway_point.x / 2) - 1) + halfway_point.x),
-            halfway_point.y,
-        )
-            .into();
-        for (i, char) in "Retry?".chars().enumerate() {
+        let retry_label = if self.is_replay { "Replay?" } else { "Retry?" };
+        let right_width = size.width - line_x - 1;
+        let retry_x = line_x + 1 + (right_width - retry_label.len() as i32).max(0) / 2;
+        for (i, char) in retry_label.chars().take(right_width as usize).enumerate() {
             updates.push(UpdateElement {
-                point: retry_point + (i as i32, 0).into(),
-                value: char,
-                fg: None,
+                point: (retry_x + i as i32, row).into(),
+                value: char.to_string(),
+                style: Style::default(),
             });
         }
-        for i in halfway_point.x - 1..size.width {
-            self.retry_button_location.push((i, halfway_point.y).into());
+        for x in line_x - 1..size.width {
+            self.retry_button_location.push((x, row).into());
         }
         Ok(())
     }
 
-    fn load_best_score(&mut self, current_score: u64) {
-        let mut path = current_exe().unwrap();
-        path.pop();
-        path.push("minesweeper_top_score.yaml");
-        if !Path::new(&path.clone()).exists() {
-            write(
-                &path.clone(),
-                serde_yaml::to_string(&self.top_score_data).expect(""),
-            )
-            .expect("")
-        }
-        let file = File::open(path.clone());
-        let mut top_score_data: TopScore = if let Ok(f) = file {
-            serde_yaml::from_reader(f).expect("")
-        } else {
-            self.top_score_data.clone()
+    // The statistics are not saved when the file could not be read, so they are never replaced
+    // by the stats of just this game.
+    fn save_stats(&mut self, won: bool, time: Option<u64>) -> Result<()> {
+        let mut stats = MinesweeperStats::load(&self.data_store)?;
+        self.stats_before_save = Some(stats.get(self.game_type).cloned().unwrap_or_default());
+        stats.record(self.game_type, won, time);
+        self.best_time = stats.get(self.game_type).and_then(|s| s.get_best_time());
+        stats.save(&self.data_store)
+    }
+
+    // The game goes on after an undone loss, so it is saved again once it is complete.
+    fn undo_save(&mut self) -> Result<()> {
+        self.saved = false;
+        let stats_before_save = match self.stats_before_save.take() {
+            Some(stats_before_save) => stats_before_save,
+            None => return Ok(()),
+        };
+        let mut stats = MinesweeperStats::load(&self.data_store)?;
+        match stats_before_save.played {
+            0 => stats.difficulties.remove(&self.game_type),
+            _ => stats.difficulties.insert(self.game_type, stats_before_save),
         };
+        self.best_time = None;
+        stats.save(&self.data_store)
+    }
 
-        let top_score_for_game_type = match top_score_data.scores.get(&self.game_type) {
-            None => current_score,
-            Some(result) => {
-                let owned_result = result.to_owned();
-                if current_score < owned_result {
-                    current_score
-                } else {
-                    owned_result
+    // Asks for a name when the win is fast enough for the leaderboard, offering the name given last time.
+    fn ask_for_name(&mut self, time_millis: u64) -> Result<()> {
+        let leaderboard = Leaderboard::load(&self.data_store)?;
+        if !leaderboard.is_on_leaderboard(self.game_type, time_millis) {
+            return Ok(());
+        }
+        let width = self.get_size().width - NAME_LABEL.len() as i32;
+        let mut name_input = TextInputComponent::new(width, MAX_NAME_LEN, None);
+        Component::<ClickAction>::set_theme(&mut name_input, &self.theme);
+        let name = leaderboard.last_name.or_else(|| std::env::var("USER").ok()).unwrap_or_default();
+        name_input.set_value(&name);
+        self.name_input = Some(name_input);
+        self.pending_entry = Some(LeaderboardEntry {
+            name: String::new(),
+            time_millis,
+            date: now(),
+        });
+        Ok(())
+    }
+
+    // Adds the pending win under the typed name. An empty name skips it.
+    fn submit_name(&mut self) -> Result<()> {
+        let name = match self.name_input.take() {
+            Some(name_input) => name_input.get_value().trim().to_string(),
+            None => return Ok(()),
+        };
+        let entry = match self.pending_entry.take() {
+            Some(entry) if !name.is_empty() => LeaderboardEntry { name: name.clone(), ..entry },
+            _ => return Ok(()),
+        };
+        let mut leaderboard = Leaderboard::load(&self.data_store)?;
+        leaderboard.add(self.game_type, entry);
+        leaderboard.last_name = Some(name);
+        leaderboard.save(&self.data_store)
+    }
+
+    fn handle_name_key(&mut self, key: KeyAction) -> Result<Option<Vec<ClickAction>>> {
+        match key {
+            KeyAction::Enter => {
+                let mut click_actions = vec![Refresh];
+                if let Err(e) = self.submit_name() {
+                    error!("Could not save the leaderboard: {}", e);
+                    click_actions.push(ClickAction::ShowError(e.to_string()));
                 }
+                Ok(Some(click_actions))
             }
-        };
-        top_score_data
-            .scores
-            .insert(self.game_type, top_score_for_game_type);
-        self.top_score_data = top_score_data;
-        write(
-            &path.clone(),
-            serde_yaml::to_string(&self.top_score_data).expect(""),
-        )
-        .expect("");
-    }
-
-    fn get_emoji_from_chill_factor(chill_factor: ZeroToEight) -> char {
-        match chill_factor {
-            Zero => '😊',
-            One => '🙂',
-            Two => '😐',
-            Three => '😕',
-            Four => '😟',
-            Five => '😩',
-            Six => '😱',
-            Seven => '🤯',
-            Eight => '🙃',
+            KeyAction::Escape => {
+                self.name_input = None;
+                self.pending_entry = None;
+                Ok(Some(vec![Refresh]))
+            }
+            _ => match self.name_input.as_mut() {
+                Some(name_input) => Ok(name_input.handle_key(key)?.map(|_| vec![Refresh])),
+                None => Ok(None),
+            },
         }
     }
 
@@ -348,12 +582,9 @@ impl GameView {
         variable: V,
         callback: fn(variable: V, engine: &mut Box<dyn CanBeEngine>, p: Point) -> Result<T>,
     ) -> Result<T> {
-        let (mut x, mut y) = point.into();
-        if x % 2 == 1 {
-            x -= 1;
-        }
-        x /= 2;
-        y -= 2;
+        let (x, y) = point.into();
+        let x = x.div_euclid(self.glyphs.cell_width);
+        let y = y - 2;
 
         if x >= 0 && x < self.engine_size.width && y >= 0 && y < self.engine_size.height {
             return callback(variable, &mut self.engine, (x, y).into());
@@ -361,6 +592,41 @@ impl GameView {
         Ok(T::default())
     }
 
+    // Saves the replay and score once the game is complete. Anything that could not be saved is
+    // shown to the player rather than ending the game.
+    fn handle_move_result(&mut self, move_result: GameState) -> Vec<ClickAction> {
+        let mut click_actions = vec![];
+        if self.engine.get_game_stats().no_guess_fallback && !self.no_guess_fallback_shown {
+            self.no_guess_fallback_shown = true;
+            click_actions.push(ClickAction::ShowNotice("No board without guessing was found in time, this one may need a guess".to_string()));
+        }
+        if let (Complete(result), false) = (move_result, self.saved) {
+            self.saved = true;
+            if let Err(e) = self.save_replay() {
+                error!("Could not save the replay: {}", e);
+                click_actions.push(ClickAction::ShowError(e.to_string()));
+            }
+            let game_stats = self.engine.get_game_stats();
+            let won = result == CompleteState::Win;
+            // Wins with undo count as wins, but their times do not count.
+            let time = match game_stats.used_undo {
+                true => None,
+                false => Some(game_stats.game_run_time_millis),
+            };
+            if let Err(e) = self.save_stats(won, time) {
+                error!("Could not save the statistics: {}", e);
+                click_actions.push(ClickAction::ShowError(e.to_string()));
+            }
+            if let (true, Some(time)) = (won, time) {
+                if let Err(e) = self.ask_for_name(time) {
+                    error!("Could not load the leaderboard: {}", e);
+                    click_actions.push(ClickAction::ShowError(e.to_string()));
+                }
+            }
+        }
+        click_actions
+    }
+
     fn get_update_elements(
         &mut self,
         board_state_getter: fn(&mut Box<dyn CanBeEngine>) -> HashMap<Cell, CellState>,
@@ -370,8 +636,9 @@ impl GameView {
         let game_stats = self.engine.get_game_stats();
         self.get_stats_board_updates(game_stats, &mut updates)?;
         self.append_updates_from_engine(&game_updates, &mut updates)?;
+        self.append_overlay_updates(&mut updates)?;
         if let Complete(_) = game_stats.game_state {
-            self.append_complete_menu_updates(game_stats, &mut updates)?;
+            self.append_complete_menu_updates(&mut updates)?;
         } else {
             self.retry_button_location.clear();
         }
@@ -402,10 +669,23 @@ impl Component<ClickAction> for GameView {
             MouseAction::Middle(p) => (Some(MoveType::Flag), p),
             MouseAction::Right(p) => (Some(MoveType::Flag), p),
             MouseAction::Left(p) => {
-                if self.retry_button_location.contains(&click.to_point()) {
+                let face_point = self.get_face_point();
+                let name_x = NAME_LABEL.len() as i32;
+                let on_name_row = p.y == self.get_size().height - 1;
+                if let (Some(name_input), true) = (self.name_input.as_mut(), on_name_row) {
+                    if p.x >= name_x {
+                        name_input.handle_click(MouseAction::Left((p.x - name_x, 0).into()))?;
+                        click_actions.push(Refresh);
+                    }
+                    (None, (0, 0).into())
+                } else if self.retry_button_location.contains(&click.to_point()) {
                     self.reset();
                     click_actions.push(Refresh);
                     (None, (0, 0).into())
+                } else if p == face_point || p == face_point + (1, 0).into() {
+                    // Clicking the face asks for a hint.
+                    self.show_hint()?;
+                    (None, (0, 0).into())
                 } else {
                     (Some(MoveType::Dig), p)
                 }
@@ -419,19 +699,245 @@ impl Component<ClickAction> for GameView {
             MouseAction::Double(p) => (Some(MoveType::DigAround), p),
             _ => (None, (0, 0).into()),
         };
+        if self.is_replay {
+            return Ok(click_actions);
+        }
         if let Some(mov) = move_type {
+            self.clear_hint();
             let move_result = self.do_action_on_point_on_engine(point, mov, |m, e, p| {
                 e.play_move(m, Cell { x: p.x, y: p.y })
             })?;
-            if let Complete(result) = move_result {
-                let score = if result == CompleteState::Win {
-                    self.engine.get_game_stats().game_run_time
-                } else {
-                    u64::MAX
-                };
-                self.load_best_score(score);
-            }
+            click_actions.append(&mut self.handle_move_result(move_result));
         }
         Ok(click_actions)
     }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<ClickAction>>> {
+        if self.name_input.is_some() {
+            return self.handle_name_key(key);
+        }
+        let keys = &self.config.keys;
+        let move_type = match key {
+            k if keys.left.contains(&k) => return self.move_cursor(-1, 0).map(|_| Some(vec![])),
+            k if keys.down.contains(&k) => return self.move_cursor(0, 1).map(|_| Some(vec![])),
+            k if keys.up.contains(&k) => return self.move_cursor(0, -1).map(|_| Some(vec![])),
+            k if keys.right.contains(&k) => return self.move_cursor(1, 0).map(|_| Some(vec![])),
+            k if keys.dig.contains(&k) => MoveType::Dig,
+            k if keys.flag.contains(&k) => MoveType::Flag,
+            k if keys.dig_around.contains(&k) => MoveType::DigAround,
+            k if keys.hint.contains(&k) => {
+                self.show_hint()?;
+                return Ok(Some(vec![]));
+            }
+            k if keys.retry.contains(&k) && !self.retry_button_location.is_empty() => {
+                self.reset();
+                return Ok(Some(vec![Refresh]));
+            }
+            // A win is final, so only a loss can be undone once the game is complete.
+            k if keys.undo.contains(&k) && !self.is_replay => {
+                let game_state = self.engine.get_game_stats().game_state;
+                if game_state == Complete(CompleteState::Win) {
+                    return Ok(Some(vec![]));
+                }
+                self.clear_hint();
+                let mut click_actions = vec![];
+                let undone_state = self.engine.undo()?;
+                if matches!(game_state, Complete(_)) && !matches!(undone_state, Complete(_)) {
+                    if let Err(e) = self.undo_save() {
+                        error!("Could not save the statistics: {}", e);
+                        click_actions.push(ClickAction::ShowError(e.to_string()));
+                    }
+                }
+                click_actions.push(Refresh);
+                return Ok(Some(click_actions));
+            }
+            k if keys.redo.contains(&k) && !self.is_replay => {
+                self.clear_hint();
+                let move_result = self.engine.redo()?;
+                let mut click_actions = self.handle_move_result(move_result);
+                click_actions.push(Refresh);
+                return Ok(Some(click_actions));
+            }
+            k if keys.faster.contains(&k) && self.is_replay => {
+                self.change_replay_speed(1);
+                return Ok(Some(vec![]));
+            }
+            k if keys.slower.contains(&k) && self.is_replay => {
+                self.change_replay_speed(-1);
+                return Ok(Some(vec![]));
+            }
+            k if keys.glyph_set.contains(&k) => {
+                self.switch_glyph_set();
+                return Ok(Some(vec![Refresh]));
+            }
+            _ => return Ok(None),
+        };
+        // The first key press only shows the cursor, so nothing is played on a cell that was not picked.
+        let cursor = match self.cursor {
+            Some(cursor) => cursor,
+            None => {
+                self.move_cursor(0, 0)?;
+                return Ok(Some(vec![]));
+            }
+        };
+        if !self.is_replay {
+            self.clear_hint();
+            let move_result = self.engine.play_move(move_type, cursor)?;
+            return Ok(Some(self.handle_move_result(move_result)));
+        }
+        Ok(Some(vec![]))
+    }
+
+    // Replays play on by themselves until they are complete, live games only need the clock.
+    fn get_time_until_tick(&self, now: Instant) -> Option<Duration> {
+        let interval = match (self.engine.get_game_stats().game_state, self.is_replay) {
+            (Complete(_), _) => return None,
+            (_, true) => REPLAY_TICK,
+            (Playing, false) => CLOCK_TICK,
+            (_, false) => return None,
+        };
+        Some((self.last_tick + interval).saturating_duration_since(now))
+    }
+
+    fn on_tick(&mut self, now: Instant) -> Result<Vec<ClickAction>> {
+        self.last_tick = now;
+        Ok(vec![])
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+        if let Some(name_input) = self.name_input.as_mut() {
+            Component::<ClickAction>::set_theme(name_input, theme);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use super::*;
+
+    // A 4x4 board with mines in the top left and bottom right corners.
+    fn corner_mines_view(data_dir: &TempDir) -> GameView {
+        let context = GameContext { data_dir: Some(data_dir.path().to_path_buf()), ..GameContext::default() };
+        let engine = Engine::new_with_layout(4, 4, &[Cell {x: 0, y: 0}, Cell {x: 3, y: 3}], 0).unwrap();
+        GameView::new_with_engine(GameType::Small, Box::from(engine), &context, false)
+    }
+
+    fn click(view: &mut GameView, button: fn(Point) -> MouseAction, cell: Cell) {
+        let point = (cell.x * view.glyphs.cell_width, cell.y + 2).into();
+        view.handle_click(button(point)).unwrap();
+    }
+
+    // Flags both mines and digs a corner, which opens the rest of the board.
+    fn win(view: &mut GameView, use_undo: bool) {
+        click(view, MouseAction::Right, Cell {x: 0, y: 0});
+        if use_undo {
+            view.handle_key(KeyAction::Char('u')).unwrap();
+            click(view, MouseAction::Right, Cell {x: 0, y: 0});
+        }
+        click(view, MouseAction::Right, Cell {x: 3, y: 3});
+        click(view, MouseAction::Left, Cell {x: 3, y: 0});
+        assert_eq!(view.engine.get_game_stats().game_state, Complete(CompleteState::Win));
+    }
+
+    #[test]
+    fn win_without_undo_sets_a_best_time() {
+        let data_dir = TempDir::new().unwrap();
+        let mut view = corner_mines_view(&data_dir);
+        win(&mut view, false);
+        let stats = MinesweeperStats::load(&view.data_store).unwrap();
+        let difficulty_stats = stats.get(GameType::Small).unwrap();
+        assert_eq!(difficulty_stats.wins, 1);
+        assert!(difficulty_stats.get_best_time().is_some());
+        assert!(view.name_input.is_some());
+    }
+
+    #[test]
+    fn win_with_undo_is_left_out_of_best_times() {
+        let data_dir = TempDir::new().unwrap();
+        let mut view = corner_mines_view(&data_dir);
+        win(&mut view, true);
+        let stats = MinesweeperStats::load(&view.data_store).unwrap();
+        let difficulty_stats = stats.get(GameType::Small).unwrap();
+        assert_eq!(difficulty_stats.wins, 1);
+        assert_eq!(difficulty_stats.get_best_time(), None);
+        assert!(view.best_time.is_none());
+        // Too slow for the leaderboard, however fast it was.
+        assert!(view.name_input.is_none());
+    }
+
+    #[test]
+    fn replay_speed_keys_step_through_the_speeds() {
+        let data_dir = TempDir::new().unwrap();
+        let mut view = corner_mines_view(&data_dir);
+        win(&mut view, false);
+        let replay = view.engine.get_replay().unwrap();
+        let player = ReplayPlayer::new(replay, 1.0).unwrap();
+        let mut replay_view = GameView::new_with_engine(GameType::Small, Box::from(player), &GameContext::default(), true);
+        replay_view.handle_key(KeyAction::Char('+')).unwrap();
+        assert_eq!(replay_view.engine.get_speed(), Some(2.0));
+        for _ in 0..REPLAY_SPEEDS.len() {
+            replay_view.handle_key(KeyAction::Char('-')).unwrap();
+        }
+        assert_eq!(replay_view.engine.get_speed(), Some(0.25));
+        // Live games have no speed, so the keys do nothing.
+        let mut live_view = corner_mines_view(&data_dir);
+        assert_eq!(live_view.handle_key(KeyAction::Char('+')).unwrap(), None);
+        assert_eq!(live_view.engine.get_speed(), None);
+    }
+
+    #[test]
+    fn undo_key_takes_back_a_losing_dig() {
+        let data_dir = TempDir::new().unwrap();
+        let mut view = corner_mines_view(&data_dir);
+        click(&mut view, MouseAction::Left, Cell {x: 1, y: 0});
+        click(&mut view, MouseAction::Left, Cell {x: 0, y: 0});
+        assert_eq!(view.engine.get_game_stats().game_state, Complete(CompleteState::Lose));
+        assert_eq!(view.handle_key(KeyAction::Char('u')).unwrap(), Some(vec![Refresh]));
+        assert_eq!(view.engine.get_game_stats().game_state, Playing);
+        assert_eq!(view.get_size().height, 4 + 2);
+        // The loss is taken back with it.
+        let stats = MinesweeperStats::load(&view.data_store).unwrap();
+        assert_eq!(stats.get(GameType::Small), None);
+        view.handle_key(KeyAction::Char('y')).unwrap();
+        assert_eq!(view.engine.get_game_stats().game_state, Complete(CompleteState::Lose));
+        let stats = MinesweeperStats::load(&view.data_store).unwrap();
+        assert_eq!(stats.get(GameType::Small).unwrap().get_losses(), 1);
+    }
+
+    #[test]
+    fn win_after_an_undone_loss_is_saved() {
+        let data_dir = TempDir::new().unwrap();
+        let mut view = corner_mines_view(&data_dir);
+        win(&mut view, false);
+        let mut view = corner_mines_view(&data_dir);
+        click(&mut view, MouseAction::Left, Cell {x: 1, y: 0});
+        click(&mut view, MouseAction::Left, Cell {x: 0, y: 0});
+        let lost_replay: ReplayFile = view.data_store.load(LAST_REPLAY_FILE_NAME).unwrap().unwrap();
+        view.handle_key(KeyAction::Char('u')).unwrap();
+        win(&mut view, false);
+
+        let stats = MinesweeperStats::load(&view.data_store).unwrap();
+        let difficulty_stats = stats.get(GameType::Small).unwrap();
+        assert_eq!((difficulty_stats.played, difficulty_stats.wins), (2, 2));
+        assert_eq!(difficulty_stats.current_streak, 2);
+        // The first win keeps its best time, the win with undo adds none.
+        assert_eq!(difficulty_stats.win_times_millis.len(), 1);
+        assert!(view.best_time.is_some());
+        let won_replay: ReplayFile = view.data_store.load(LAST_REPLAY_FILE_NAME).unwrap().unwrap();
+        assert!(won_replay.replay.moves.len() > lost_replay.replay.moves.len());
+    }
+
+    #[test]
+    fn undo_does_nothing_after_a_win() {
+        let data_dir = TempDir::new().unwrap();
+        let mut view = corner_mines_view(&data_dir);
+        win(&mut view, false);
+        view.handle_key(KeyAction::Escape).unwrap();
+        assert_eq!(view.handle_key(KeyAction::Char('u')).unwrap(), Some(vec![]));
+        assert_eq!(view.engine.get_game_stats().game_state, Complete(CompleteState::Win));
+        let stats = MinesweeperStats::load(&view.data_store).unwrap();
+        assert_eq!(stats.get(GameType::Small).unwrap().wins, 1);
+    }
 }
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::path::Path;
+use crossterm::{ErrorKind, Result};
+use game_actions::data_store::DataStore;
+use serde::{Deserialize, Serialize};
+use crate::game_type::GameType;
+use crate::leaderboard_entry::LeaderboardEntry;
+use crate::minesweeper_stats::list_game_types;
+
+pub const LEADERBOARD_FILE_NAME: &str = "minesweeper_leaderboard.yaml";
+pub const LEADERBOARD_SIZE: usize = 10;
+const LEADERBOARD_VERSION: u32 = 1;
+pub const MAX_NAME_LEN: usize = 16;
+
+// The fastest named wins of each difficulty, which can be exported and merged with the
+// leaderboard of another machine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub version: u32,
+    // Name given for the last entry, offered again for the next one. Not exported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(with = "crate::game_type_map")]
+    pub difficulties: HashMap<GameType, Vec<LeaderboardEntry>>,
+}
+
+impl Leaderboard {
+    pub fn new() -> Self {
+        Leaderboard {
+            version: LEADERBOARD_VERSION,
+            last_name: None,
+            difficulties: HashMap::new(),
+        }
+    }
+
+    pub fn load(data_store: &DataStore) -> Result<Leaderboard> {
+        Ok(data_store.load(LEADERBOARD_FILE_NAME)?.unwrap_or_default())
+    }
+
+    pub fn save(&self, data_store: &DataStore) -> Result<()> {
+        data_store.save(LEADERBOARD_FILE_NAME, self)
+    }
+
+    // Fastest first, where the earlier of two equal times ranks higher.
+    pub fn get_entries(&self, game_type: GameType) -> &[LeaderboardEntry] {
+        self.difficulties.get(&game_type).map_or(&[], |entries| entries.as_slice())
+    }
+
+    pub fn get_game_types(&self) -> Vec<GameType> {
+        list_game_types(self.difficulties.keys())
+    }
+
+    pub fn is_on_leaderboard(&self, game_type: GameType, time_millis: u64) -> bool {
+        let entries = self.get_entries(game_type);
+        entries.len() < LEADERBOARD_SIZE || entries.iter().any(|entry| time_millis < entry.time_millis)
+    }
+
+    // Returns the 1-based rank of the entry, or None when it was not fast enough.
+    pub fn add(&mut self, game_type: GameType, entry: LeaderboardEntry) -> Option<usize> {
+        let entries = self.difficulties.entry(game_type).or_default();
+        if entries.contains(&entry) {
+            return None;
+        }
+        let idx = entries.partition_point(|e| (e.time_millis, e.date) <= (entry.time_millis, entry.date));
+        if idx >= LEADERBOARD_SIZE {
+            return None;
+        }
+        entries.insert(idx, entry);
+        entries.truncate(LEADERBOARD_SIZE);
+        Some(idx + 1)
+    }
+
+    // Adds every entry of the other leaderboard that is fast enough, with names cut to MAX_NAME_LEN. Entries that are already
+    // here are skipped, so the same file can be imported twice. Returns how many were added.
+    pub fn merge(&mut self, other: Leaderboard) -> usize {
+        let mut added = 0;
+        for (game_type, entries) in other.difficulties {
+            for mut entry in entries {
+                entry.name = entry.name.chars().take(MAX_NAME_LEN).collect();
+                if self.add(game_type, entry).is_some() {
+                    added += 1;
+                }
+            }
+        }
+        added
+    }
+
+    pub fn export(&self, path: &Path) -> Result<()> {
+        let leaderboard = Leaderboard {
+            last_name: None,
+            ..self.clone()
+        };
+        let (data_store, file_name) = get_file_store(path)?;
+        data_store.save(file_name, &leaderboard)
+    }
+
+    pub fn import(path: &Path) -> Result<Leaderboard> {
+        let (data_store, file_name) = get_file_store(path)?;
+        let leaderboard: Leaderboard = data_store.load(file_name)?
+            .ok_or_else(|| ErrorKind::other(format!("{} does not exist", path.display())))?;
+        if leaderboard.version > LEADERBOARD_VERSION {
+            Err(ErrorKind::other(format!(
+                "Leaderboard version {} is not supported, expected version {} or older",
+                leaderboard.version, LEADERBOARD_VERSION
+            )))?;
+        }
+        Ok(leaderboard)
+    }
+}
+
+// A store for the directory of the file, so exports are written atomically as well.
+fn get_file_store(path: &Path) -> Result<(DataStore, &str)> {
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let file_name = path.file_name().and_then(|f| f.to_str())
+        .ok_or_else(|| ErrorKind::other(format!("{} is not a file name", path.display())))?;
+    Ok((DataStore::new(dir.to_path_buf()), file_name))
+}
+
+impl Default for Leaderboard {
+    fn default() -> Self {
+        Leaderboard::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, time_millis: u64, date: u64) -> LeaderboardEntry {
+        LeaderboardEntry { name: name.to_string(), time_millis, date }
+    }
+
+    fn small_leaderboard(entries: Vec<LeaderboardEntry>) -> Leaderboard {
+        let mut leaderboard = Leaderboard::new();
+        for entry in entries {
+            leaderboard.add(GameType::Small, entry);
+        }
+        leaderboard
+    }
+
+    fn get_times(leaderboard: &Leaderboard) -> Vec<u64> {
+        leaderboard.get_entries(GameType::Small).iter().map(|entry| entry.time_millis).collect()
+    }
+
+    #[test]
+    fn merging_the_same_entries_again_adds_nothing() {
+        let mut leaderboard = small_leaderboard(vec![entry("ann", 9000, 1), entry("bob", 7000, 2)]);
+        let other = leaderboard.clone();
+        assert_eq!(leaderboard.merge(other), 0);
+        assert_eq!(get_times(&leaderboard), vec![7000, 9000]);
+
+        let other = small_leaderboard(vec![entry("bob", 7000, 2), entry("cat", 8000, 3)]);
+        assert_eq!(leaderboard.merge(other), 1);
+        assert_eq!(get_times(&leaderboard), vec![7000, 8000, 9000]);
+    }
+
+    #[test]
+    fn only_the_fastest_entries_are_kept() {
+        let mut leaderboard = small_leaderboard((0..LEADERBOARD_SIZE as u64).map(|i| entry("ann", 10_000 + i, 1)).collect());
+        let other = small_leaderboard(vec![entry("bob", 5000, 2), entry("cat", 20_000, 3)]);
+        assert_eq!(leaderboard.merge(other), 1);
+        let times = get_times(&leaderboard);
+        assert_eq!(times.len(), LEADERBOARD_SIZE);
+        assert_eq!(times[0], 5000);
+        assert_eq!(times[LEADERBOARD_SIZE - 1], 10_008);
+    }
+
+    #[test]
+    fn equal_times_rank_the_earlier_win_first() {
+        let mut leaderboard = small_leaderboard(vec![entry("ann", 9000, 200)]);
+        let other = small_leaderboard(vec![entry("bob", 9000, 100), entry("cat", 9000, 300)]);
+        assert_eq!(leaderboard.merge(other), 2);
+        let names: Vec<&str> = leaderboard.get_entries(GameType::Small).iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, vec!["bob", "ann", "cat"]);
+    }
+
+    #[test]
+    fn long_names_are_cut_when_merged() {
+        let mut leaderboard = Leaderboard::new();
+        let other = small_leaderboard(vec![entry(&"é".repeat(MAX_NAME_LEN + 4), 9000, 1)]);
+        assert_eq!(leaderboard.merge(other.clone()), 1);
+        assert_eq!(leaderboard.get_entries(GameType::Small)[0].name, "é".repeat(MAX_NAME_LEN));
+        // The cut name is the same entry when the file is merged again.
+        assert_eq!(leaderboard.merge(other), 0);
+    }
+
+    #[test]
+    fn each_difficulty_is_merged_on_its_own() {
+        let mut leaderboard = small_leaderboard(vec![entry("ann", 9000, 1)]);
+        let mut other = Leaderboard::new();
+        other.add(GameType::Large, entry("ann", 9000, 1));
+        assert_eq!(leaderboard.merge(other), 1);
+        assert_eq!(leaderboard.get_entries(GameType::Large).len(), 1);
+        assert_eq!(get_times(&leaderboard), vec![9000]);
+    }
+}
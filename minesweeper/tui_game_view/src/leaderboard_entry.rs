@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub time_millis: u64,
+    // When the game was won, in seconds since the Unix epoch.
+    pub date: u64,
+}
@@ -0,0 +1,122 @@
+use crate::date::{format_date, format_time};
+use crate::game_type::GameType;
+use crate::leaderboard::{Leaderboard, LEADERBOARD_SIZE, MAX_NAME_LEN};
+use crossterm::Result;
+use game_actions::click_action::ClickAction;
+use game_actions::click_action::ClickAction::Refresh;
+use game_actions::config::minesweeper_keys::MinesweeperKeys;
+use game_actions::data_store::DataStore;
+use game_actions::game_context::GameContext;
+use tui::screen::dimension::Dimension;
+use tui::screen::point::Point;
+use tui::screen::style::Style;
+use tui::screen::text;
+use tui::screen::window::component::Component;
+use tui::screen::window::key_action::KeyAction;
+use tui::screen::window::mouse_action::MouseAction;
+use tui::screen::window::update_element::UpdateElement;
+use uuid::Uuid;
+
+const VIEW_WIDTH: i32 = 44;
+const ENTRIES_ROW: i32 = 3;
+
+// The ranked leaderboard of one difficulty at a time. Left and Right, or clicking the arrows,
+// switch between the difficulties.
+pub struct LeaderboardView {
+    id: Uuid,
+    data_store: DataStore,
+    leaderboard: Leaderboard,
+    selected: usize,
+    keys: MinesweeperKeys,
+}
+
+impl LeaderboardView {
+    pub fn new(context: &GameContext) -> Result<Self> {
+        let data_store = context.get_data_store();
+        Ok(LeaderboardView {
+            id: Uuid::new_v4(),
+            leaderboard: Leaderboard::load(&data_store)?,
+            data_store,
+            selected: 0,
+            keys: context.config.minesweeper.keys.clone(),
+        })
+    }
+
+    fn get_game_type(&self) -> GameType {
+        let game_types = self.leaderboard.get_game_types();
+        game_types[self.selected % game_types.len()]
+    }
+
+    // Loads the leaderboard again, as games may have been won since the window opened.
+    fn select(&mut self, step: i32) -> Vec<ClickAction> {
+        if let Ok(leaderboard) = Leaderboard::load(&self.data_store) {
+            self.leaderboard = leaderboard;
+        }
+        let count = self.leaderboard.get_game_types().len() as i32;
+        self.selected = (self.selected as i32 + step).rem_euclid(count) as usize;
+        vec![Refresh]
+    }
+
+    fn push_line(updates: &mut Vec<UpdateElement>, y: i32, text: &str) {
+        updates.append(&mut text::layout(&text::fit(text, VIEW_WIDTH), (0, y).into(), VIEW_WIDTH, Style::default()));
+    }
+}
+
+impl Component<ClickAction> for LeaderboardView {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        (VIEW_WIDTH, ENTRIES_ROW + LEADERBOARD_SIZE as i32).into()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        let mut updates = vec![];
+        let game_type = self.get_game_type();
+        let label = game_type.get_label();
+        let header = format!("<{:^w$}>", label, w = VIEW_WIDTH as usize - 2);
+        LeaderboardView::push_line(&mut updates, 0, &header);
+        let columns = format!("{:>3}  {:<w$} {:>9}  {:<10}", "#", "Name", "Time", "Date", w = MAX_NAME_LEN);
+        LeaderboardView::push_line(&mut updates, 1, &columns);
+        LeaderboardView::push_line(&mut updates, 2, &"─".repeat(VIEW_WIDTH as usize));
+
+        let entries = self.leaderboard.get_entries(game_type);
+        for i in 0..LEADERBOARD_SIZE {
+            let line = match (entries.get(i), i) {
+                (Some(entry), _) => format!(
+                    "{:>3}. {} {:>9}  {}",
+                    i + 1,
+                    text::fit(&entry.name, MAX_NAME_LEN as i32),
+                    format_time(entry.time_millis),
+                    format_date(entry.date)
+                ),
+                (None, 0) => "No wins yet".to_string(),
+                (None, _) => String::new(),
+            };
+            LeaderboardView::push_line(&mut updates, ENTRIES_ROW + i as i32, &line);
+        }
+        Ok(updates)
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        Ok(vec![])
+    }
+
+    fn handle_click(&mut self, click: MouseAction) -> Result<Vec<ClickAction>> {
+        let point: Point = click.to_point();
+        Ok(match click {
+            MouseAction::Left(_) if point == (0, 0).into() => self.select(-1),
+            MouseAction::Left(_) if point == (VIEW_WIDTH - 1, 0).into() => self.select(1),
+            _ => vec![],
+        })
+    }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<ClickAction>>> {
+        Ok(match key {
+            k if self.keys.left.contains(&k) => Some(self.select(-1)),
+            k if self.keys.right.contains(&k) => Some(self.select(1)),
+            _ => None,
+        })
+    }
+}
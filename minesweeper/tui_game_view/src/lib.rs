@@ -1,3 +1,18 @@
+pub mod best_time;
+pub mod board_glyphs;
+pub mod custom_game_dialog;
+pub mod date;
+pub mod difficulty_stats;
+pub mod game_type;
+pub mod game_type_map;
 pub mod game_view;
+pub mod leaderboard;
+pub mod leaderboard_entry;
+pub mod leaderboard_view;
+pub mod minesweeper_descriptor;
+pub mod minesweeper_stats;
+pub mod minesweeper_variant;
+pub mod replay_file;
+pub mod stats_view;
 mod top_score;
 mod test_engine;
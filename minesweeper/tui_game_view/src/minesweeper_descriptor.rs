@@ -0,0 +1,144 @@
+use crate::custom_game_dialog::CustomGameDialog;
+use crate::game_type::GameType;
+use crate::game_view::{GameView, LAST_REPLAY_FILE_NAME};
+use crate::leaderboard::{Leaderboard, LEADERBOARD_FILE_NAME};
+use crate::leaderboard_view::LeaderboardView;
+use crate::minesweeper_stats::{STATS_FILE_NAME, TOP_SCORE_FILE_NAME};
+use crate::minesweeper_variant::MinesweeperVariant;
+use crate::stats_view::StatsView;
+use crossterm::{ErrorKind, Result};
+use game_actions::click_action::ClickAction;
+use game_actions::game_context::GameContext;
+use game_actions::game_descriptor::GameDescriptor;
+use game_actions::game_variant::GameVariant;
+use game_actions::start_options::StartOptions;
+use std::path::Path;
+use tui::screen::window::component::Component;
+
+pub const MINESWEEPER_ID: &str = "minesweeper";
+const REPLAY_SPEED: f64 = 1.0;
+
+pub struct MinesweeperDescriptor;
+
+impl GameDescriptor for MinesweeperDescriptor {
+    fn get_id(&self) -> &'static str {
+        MINESWEEPER_ID
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Minesweeper"
+    }
+
+    fn get_variants(&self) -> Vec<GameVariant> {
+        vec![
+            MinesweeperVariant::Play(GameType::Small).into(),
+            MinesweeperVariant::Play(GameType::Medium).into(),
+            MinesweeperVariant::Play(GameType::Large).into(),
+            MinesweeperVariant::Custom.into(),
+            MinesweeperVariant::Replay.into(),
+            MinesweeperVariant::Stats.into(),
+            MinesweeperVariant::Leaderboard.into(),
+        ]
+    }
+
+    fn get_variant_label(&self, variant: &GameVariant) -> String {
+        match MinesweeperVariant::parse(variant) {
+            Ok(MinesweeperVariant::Play(game_type)) => game_type.get_label(),
+            Ok(MinesweeperVariant::Custom) => "Custom".to_string(),
+            Ok(MinesweeperVariant::Replay) => "Replay".to_string(),
+            Ok(MinesweeperVariant::Stats) => "Stats".to_string(),
+            Ok(MinesweeperVariant::Leaderboard) => "Top 10".to_string(),
+            Err(_) => variant.get_id().to_string(),
+        }
+    }
+
+    fn get_window_title(&self, variant: &GameVariant) -> String {
+        match MinesweeperVariant::parse(variant) {
+            Ok(MinesweeperVariant::Custom) => "Custom game".to_string(),
+            Ok(MinesweeperVariant::Stats) => "Minesweeper statistics".to_string(),
+            Ok(MinesweeperVariant::Leaderboard) => "Minesweeper leaderboard".to_string(),
+            _ => self.get_variant_label(variant),
+        }
+    }
+
+    fn get_start_variant(&self, options: &StartOptions, context: &GameContext) -> std::result::Result<GameVariant, String> {
+        let game_type = match (options.size, &options.difficulty) {
+            (Some((width, height)), _) => GameType::Custom {
+                width,
+                height,
+                mines: options.mines.ok_or("A custom minesweeper board needs --mines as well as --width and --height")?,
+                no_guess: context.config.minesweeper.no_guess,
+            },
+            (None, Some(difficulty)) => GameType::from_difficulty(difficulty)?,
+            (None, None) => get_default_game_type(context)?,
+        };
+        Ok(MinesweeperVariant::Play(game_type).into())
+    }
+
+    fn get_data_files(&self) -> Vec<&'static str> {
+        vec![STATS_FILE_NAME, TOP_SCORE_FILE_NAME, LAST_REPLAY_FILE_NAME, LEADERBOARD_FILE_NAME]
+    }
+
+    fn create(&self, variant: &GameVariant, context: &GameContext) -> Result<Box<dyn Component<ClickAction>>> {
+        Ok(match MinesweeperVariant::parse(variant).map_err(ErrorKind::other)? {
+            MinesweeperVariant::Play(game_type) => {
+                GameView::validate_game_type(game_type)?;
+                Box::from(GameView::new_with_context(game_type, context))
+            }
+            MinesweeperVariant::Custom => {
+                let game_type = get_default_game_type(context).map_err(ErrorKind::other)?;
+                Box::from(CustomGameDialog::new(game_type, context.config.minesweeper.no_guess))
+            }
+            MinesweeperVariant::Replay => Box::from(GameView::from_last_replay(context, REPLAY_SPEED)?),
+            MinesweeperVariant::Stats => Box::from(StatsView::new(context)?),
+            MinesweeperVariant::Leaderboard => Box::from(LeaderboardView::new(context)?),
+        })
+    }
+
+    fn export_leaderboard(&self, context: &GameContext, path: &Path) -> Result<usize> {
+        let leaderboard = Leaderboard::load(&context.get_data_store())?;
+        leaderboard.export(path)?;
+        Ok(leaderboard.difficulties.values().map(|entries| entries.len()).sum())
+    }
+
+    fn import_leaderboard(&self, context: &GameContext, path: &Path) -> Result<usize> {
+        let data_store = context.get_data_store();
+        let mut leaderboard = Leaderboard::load(&data_store)?;
+        let added = leaderboard.merge(Leaderboard::import(path)?);
+        leaderboard.save(&data_store)?;
+        Ok(added)
+    }
+}
+
+fn get_default_game_type(context: &GameContext) -> std::result::Result<GameType, String> {
+    GameType::from_difficulty(&context.config.default_difficulty).map_err(|e| format!("default_difficulty: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_start_game_type(options: StartOptions) -> std::result::Result<GameType, String> {
+        let variant = MinesweeperDescriptor.get_start_variant(&options, &GameContext::default())?;
+        match MinesweeperVariant::parse(&variant)? {
+            MinesweeperVariant::Play(game_type) => Ok(game_type),
+            other => Err(format!("Expected a game, got {:?}", other)),
+        }
+    }
+
+    #[test]
+    fn custom_size_needs_mines() {
+        let options = StartOptions { size: Some((20, 10)), ..StartOptions::default() };
+        assert!(get_start_game_type(options).unwrap_err().contains("--mines"));
+
+        let options = StartOptions { size: Some((20, 10)), mines: Some(30), ..StartOptions::default() };
+        assert_eq!(get_start_game_type(options), Ok(GameType::Custom { width: 20, height: 10, mines: 30, no_guess: false }));
+    }
+
+    #[test]
+    fn default_difficulty_is_used_without_options() {
+        assert_eq!(get_start_game_type(StartOptions::default()), Ok(GameType::Medium));
+        let options = StartOptions { difficulty: Some("hard".to_string()), ..StartOptions::default() };
+        assert_eq!(get_start_game_type(options), Ok(GameType::Large));
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use crossterm::Result;
+use game_actions::data_store::DataStore;
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use crate::best_time::BestTime;
+use crate::date::now;
+use crate::difficulty_stats::DifficultyStats;
+use crate::game_type::GameType;
+use crate::top_score::TopScore;
+
+pub const STATS_FILE_NAME: &str = "minesweeper_stats.yaml";
+pub const TOP_SCORE_FILE_NAME: &str = "minesweeper_top_score.yaml";
+const STATS_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MinesweeperStats {
+    pub version: u32,
+    #[serde(with = "crate::game_type_map")]
+    pub difficulties: HashMap<GameType, DifficultyStats>,
+}
+
+impl MinesweeperStats {
+    pub fn new() -> Self {
+        MinesweeperStats {
+            version: STATS_VERSION,
+            difficulties: HashMap::new(),
+        }
+    }
+
+    // Top scores from before there were statistics are moved into a new statistics file.
+    pub fn load(data_store: &DataStore) -> Result<MinesweeperStats> {
+        if let Some(mut stats) = data_store.load::<MinesweeperStats>(STATS_FILE_NAME)? {
+            if stats.version < 2 {
+                stats.difficulties.values_mut().for_each(|d| d.convert_seconds_to_millis());
+                stats.version = STATS_VERSION;
+            }
+            return Ok(stats);
+        }
+        let mut stats = MinesweeperStats::new();
+        if let Some(top_score) = data_store.load::<TopScore>(TOP_SCORE_FILE_NAME)? {
+            stats.add_top_score(top_score);
+            stats.save(data_store)?;
+            info!("Moved the top scores to {}", data_store.get_path(STATS_FILE_NAME).display());
+            if let Err(e) = data_store.remove(TOP_SCORE_FILE_NAME) {
+                error!("{}", e);
+            }
+        }
+        Ok(stats)
+    }
+
+    pub fn save(&self, data_store: &DataStore) -> Result<()> {
+        data_store.save(STATS_FILE_NAME, self)
+    }
+
+    // Only the best time in seconds of each difficulty was kept, so it becomes a single win.
+    // A u64::MAX score was a loss and is left out.
+    fn add_top_score(&mut self, top_score: TopScore) {
+        for (game_type, score) in top_score.scores {
+            if score == u64::MAX {
+                continue;
+            }
+            let difficulty_stats = self.difficulties.entry(game_type).or_default();
+            difficulty_stats.played += 1;
+            difficulty_stats.wins += 1;
+            difficulty_stats.longest_streak = difficulty_stats.longest_streak.max(1);
+            difficulty_stats.win_times_millis.push(score * 1000);
+            difficulty_stats.add_best_time(BestTime { time_millis: score * 1000, date: None });
+        }
+    }
+
+    pub fn get(&self, game_type: GameType) -> Option<&DifficultyStats> {
+        self.difficulties.get(&game_type)
+    }
+
+    // The time is None for wins that do not count towards the times, such as wins with undo.
+    pub fn record(&mut self, game_type: GameType, won: bool, time_millis: Option<u64>) {
+        let difficulty_stats = self.difficulties.entry(game_type).or_default();
+        match won {
+            true => difficulty_stats.record_win(time_millis, now()),
+            false => difficulty_stats.record_loss(),
+        }
+    }
+
+    pub fn get_game_types(&self) -> Vec<GameType> {
+        list_game_types(self.difficulties.keys())
+    }
+}
+
+// Easy, medium and hard, followed by every custom game that was played, smallest first.
+pub(crate) fn list_game_types<'a>(played: impl Iterator<Item = &'a GameType>) -> Vec<GameType> {
+    let mut custom_types: Vec<GameType> = played
+        .filter(|game_type| matches!(game_type, GameType::Custom { .. }))
+        .copied()
+        .collect();
+    custom_types.sort_by_key(|game_type| match game_type {
+        GameType::Custom { width, height, mines, no_guess } => (width * height, *mines, *no_guess),
+        _ => (0, 0, false),
+    });
+    let mut game_types = vec![GameType::Small, GameType::Medium, GameType::Large];
+    game_types.append(&mut custom_types);
+    game_types
+}
+
+impl Default for MinesweeperStats {
+    fn default() -> Self {
+        MinesweeperStats::new()
+    }
+}
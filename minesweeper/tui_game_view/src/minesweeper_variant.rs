@@ -0,0 +1,69 @@
+use game_actions::game_variant::GameVariant;
+use crate::game_type::GameType;
+
+// The windows minesweeper opens, kept in a GameVariant by their id.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MinesweeperVariant {
+    Play(GameType),
+    // A dialog to pick the settings of a Custom game.
+    Custom,
+    // Plays back the last finished game.
+    Replay,
+    // Shows the statistics of every game played.
+    Stats,
+    // Shows the fastest named wins.
+    Leaderboard,
+}
+
+impl MinesweeperVariant {
+    // A game is kept by the key of its game type, which never clashes with the other ids.
+    pub fn parse(variant: &GameVariant) -> Result<MinesweeperVariant, String> {
+        match variant.get_id() {
+            "custom" => Ok(MinesweeperVariant::Custom),
+            "replay" => Ok(MinesweeperVariant::Replay),
+            "stats" => Ok(MinesweeperVariant::Stats),
+            "leaderboard" => Ok(MinesweeperVariant::Leaderboard),
+            key => GameType::from_key(key).map(MinesweeperVariant::Play),
+        }
+    }
+}
+
+impl From<MinesweeperVariant> for GameVariant {
+    fn from(variant: MinesweeperVariant) -> Self {
+        match variant {
+            MinesweeperVariant::Play(game_type) => GameVariant::new(game_type.to_key()),
+            MinesweeperVariant::Custom => GameVariant::new("custom"),
+            MinesweeperVariant::Replay => GameVariant::new("replay"),
+            MinesweeperVariant::Stats => GameVariant::new("stats"),
+            MinesweeperVariant::Leaderboard => GameVariant::new("leaderboard"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_variant_round_trips_through_its_id() {
+        let variants = [
+            MinesweeperVariant::Play(GameType::Small),
+            MinesweeperVariant::Play(GameType::Large),
+            MinesweeperVariant::Play(GameType::Custom { width: 20, height: 10, mines: 30, no_guess: false }),
+            MinesweeperVariant::Play(GameType::Custom { width: 20, height: 10, mines: 30, no_guess: true }),
+            MinesweeperVariant::Custom,
+            MinesweeperVariant::Replay,
+            MinesweeperVariant::Stats,
+            MinesweeperVariant::Leaderboard,
+        ];
+        for variant in variants {
+            assert_eq!(MinesweeperVariant::parse(&variant.into()), Ok(variant));
+        }
+    }
+
+    #[test]
+    fn unknown_id_is_an_error() {
+        assert!(MinesweeperVariant::parse(&GameVariant::new("small")).is_err());
+        assert!(MinesweeperVariant::parse(&GameVariant::new("Custom 20x10")).is_err());
+    }
+}
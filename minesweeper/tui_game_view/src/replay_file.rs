@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use minesweeper_engine::replay::Replay;
+use crate::game_type::GameType;
+
+// Bump this whenever the layout of the file changes in a way older versions cannot read.
+pub const REPLAY_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayFile {
+    pub version: u32,
+    pub game_type: GameType,
+    pub replay: Replay,
+}
@@ -0,0 +1,150 @@
+use crate::date::format_time;
+use crate::difficulty_stats::DifficultyStats;
+use crate::game_type::GameType;
+use crate::minesweeper_stats::MinesweeperStats;
+use crossterm::Result;
+use game_actions::click_action::ClickAction;
+use game_actions::click_action::ClickAction::Refresh;
+use game_actions::config::minesweeper_keys::MinesweeperKeys;
+use game_actions::data_store::DataStore;
+use game_actions::game_context::GameContext;
+use tui::screen::dimension::Dimension;
+use tui::screen::point::Point;
+use tui::screen::style::Style;
+use tui::screen::text;
+use tui::screen::window::component::Component;
+use tui::screen::window::key_action::KeyAction;
+use tui::screen::window::mouse_action::MouseAction;
+use tui::screen::window::update_element::UpdateElement;
+use uuid::Uuid;
+
+const VIEW_WIDTH: i32 = 34;
+const BEST_TIMES_ROW: i32 = 8;
+const BEST_TIMES_SHOWN: i32 = 10;
+
+// The statistics of one difficulty at a time. Left and Right, or clicking the arrows,
+// switch between the difficulties.
+pub struct StatsView {
+    id: Uuid,
+    data_store: DataStore,
+    stats: MinesweeperStats,
+    selected: usize,
+    keys: MinesweeperKeys,
+}
+
+fn format_optional_time(time_millis: Option<u64>) -> String {
+    match time_millis {
+        Some(time_millis) => format_time(time_millis),
+        None => "-".to_string(),
+    }
+}
+
+impl StatsView {
+    pub fn new(context: &GameContext) -> Result<Self> {
+        let data_store = context.get_data_store();
+        Ok(StatsView {
+            id: Uuid::new_v4(),
+            stats: MinesweeperStats::load(&data_store)?,
+            data_store,
+            selected: 0,
+            keys: context.config.minesweeper.keys.clone(),
+        })
+    }
+
+    fn get_game_type(&self) -> GameType {
+        let game_types = self.stats.get_game_types();
+        game_types[self.selected % game_types.len()]
+    }
+
+    // Loads the statistics again, as games may have been played since the window opened.
+    fn select(&mut self, step: i32) -> Vec<ClickAction> {
+        if let Ok(stats) = MinesweeperStats::load(&self.data_store) {
+            self.stats = stats;
+        }
+        let count = self.stats.get_game_types().len() as i32;
+        self.selected = (self.selected as i32 + step).rem_euclid(count) as usize;
+        vec![Refresh]
+    }
+
+    fn push_line(updates: &mut Vec<UpdateElement>, y: i32, text: &str) {
+        updates.append(&mut text::layout(&text::fit(text, VIEW_WIDTH), (0, y).into(), VIEW_WIDTH, Style::default()));
+    }
+
+    fn get_stat_lines(difficulty_stats: &DifficultyStats) -> Vec<String> {
+        let win_rate = match difficulty_stats.get_win_rate() {
+            Some(win_rate) => format!("{}%", win_rate),
+            None => "-".to_string(),
+        };
+        vec![
+            format!("Played  {:>8}  Win rate{:>8}", difficulty_stats.played, win_rate),
+            format!("Wins    {:>8}  Streak  {:>8}", difficulty_stats.wins, difficulty_stats.current_streak),
+            format!("Losses  {:>8}  Longest {:>8}", difficulty_stats.get_losses(), difficulty_stats.longest_streak),
+            format!(
+                "Average {:>8}  Median  {:>8}",
+                format_optional_time(difficulty_stats.get_average_win_time()),
+                format_optional_time(difficulty_stats.get_median_win_time())
+            ),
+        ]
+    }
+}
+
+impl Component<ClickAction> for StatsView {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        (VIEW_WIDTH, BEST_TIMES_ROW + BEST_TIMES_SHOWN).into()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        let mut updates = vec![];
+        let game_type = self.get_game_type();
+        let label = game_type.get_label();
+        let header = format!("<{:^w$}>", label, w = VIEW_WIDTH as usize - 2);
+        StatsView::push_line(&mut updates, 0, &header);
+        StatsView::push_line(&mut updates, 1, &"─".repeat(VIEW_WIDTH as usize));
+
+        let default_stats = DifficultyStats::default();
+        let difficulty_stats = self.stats.get(game_type).unwrap_or(&default_stats);
+        for (i, line) in StatsView::get_stat_lines(difficulty_stats).iter().enumerate() {
+            StatsView::push_line(&mut updates, 2 + i as i32, line);
+        }
+        StatsView::push_line(&mut updates, 6, &"─".repeat(VIEW_WIDTH as usize));
+
+        let title = match difficulty_stats.best_times.is_empty() {
+            true => "No wins yet",
+            false => "Best times",
+        };
+        StatsView::push_line(&mut updates, BEST_TIMES_ROW - 1, title);
+        for i in 0..BEST_TIMES_SHOWN {
+            let line = match difficulty_stats.best_times.get(i as usize) {
+                Some(best_time) => format!("{:>3}. {:>9}  {}", i + 1, format_time(best_time.time_millis), best_time.format_date()),
+                None => String::new(),
+            };
+            StatsView::push_line(&mut updates, BEST_TIMES_ROW + i, &line);
+        }
+        Ok(updates)
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        Ok(vec![])
+    }
+
+    fn handle_click(&mut self, click: MouseAction) -> Result<Vec<ClickAction>> {
+        let point: Point = click.to_point();
+        Ok(match click {
+            MouseAction::Left(_) if point == (0, 0).into() => self.select(-1),
+            MouseAction::Left(_) if point == (VIEW_WIDTH - 1, 0).into() => self.select(1),
+            _ => vec![],
+        })
+    }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<ClickAction>>> {
+        Ok(match key {
+            k if self.keys.left.contains(&k) => Some(self.select(-1)),
+            k if self.keys.right.contains(&k) => Some(self.select(1)),
+            _ => None,
+        })
+    }
+}
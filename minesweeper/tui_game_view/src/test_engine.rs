@@ -5,6 +5,8 @@ use minesweeper_engine::cell::Cell;
 use minesweeper_engine::cell_state::CellState;
 use minesweeper_engine::cell_state::CellState::{Bomb, Checked, Flagged, Unchecked};
 use minesweeper_engine::game_state::GameState;
+use minesweeper_engine::hint::Hint;
+use minesweeper_engine::replay::Replay;
 use minesweeper_engine::game_state::GameState::Playing;
 use minesweeper_engine::move_type::MoveType;
 use minesweeper_engine::zero_to_eight::ZeroToEight;
@@ -22,11 +24,11 @@ impl TestEngine {
 
 impl CanBeEngine for TestEngine {
     fn get_size(&self) -> (i32, i32) {
-        return (4,3);
+        (4,3)
     }
 
     fn get_game_stats(&self) -> GameStats {
-        return GameStats{game_state: Playing, flags_remaining:33, game_run_time: 999};
+        GameStats{game_state: Playing, flags_remaining:33, game_run_time_millis: 999_000, used_undo: false, no_guess_fallback: false}
     }
 
     fn get_board_updates(&mut self) -> HashMap<Cell, CellState> {
@@ -49,7 +51,7 @@ impl CanBeEngine for TestEngine {
             map.insert(Cell{y: 3, x: 1}, Flagged);
             map.insert(Cell{y: 3, x: 2}, Bomb);
         }
-        return map;
+        map
     }
 
     fn play_move(&mut self, _: MoveType, _: Cell) -> crossterm::Result<GameState> {
@@ -64,4 +66,23 @@ impl CanBeEngine for TestEngine {
     fn get_chill_factor(&mut self, _: Cell) -> crossterm::Result<ZeroToEight> {
         Ok(Eight)
     }
+
+    fn get_hint(&mut self) -> crossterm::Result<Hint> {
+        let mut hint = Hint::default();
+        hint.safe_cells.insert(Cell{y: 0, x: 3});
+        hint.mine_probabilities.insert(Cell{y: 0, x: 3}, 0.0);
+        Ok(hint)
+    }
+
+    fn get_replay(&self) -> Option<Replay> {
+        None
+    }
+
+    fn undo(&mut self) -> crossterm::Result<GameState> {
+        Ok(Playing)
+    }
+
+    fn redo(&mut self) -> crossterm::Result<GameState> {
+        Ok(Playing)
+    }
 }
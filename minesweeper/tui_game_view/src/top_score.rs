@@ -1,8 +1,35 @@
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
-use game_actions::game_type::GameType;
+use crate::game_type::GameType;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// How the best time of each difficulty was kept before minesweeper_stats.yaml, where a
+// u64::MAX score is a loss.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TopScore {
+    #[serde(with = "crate::game_type_map")]
     pub scores: HashMap<GameType,u64>
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_scores_round_trip_through_yaml() {
+        let top_score = TopScore {
+            scores: HashMap::from([
+                (GameType::Small, 12),
+                (GameType::Custom { width: 20, height: 10, mines: 30, no_guess: false }, 95),
+                (GameType::Custom { width: 20, height: 10, mines: 30, no_guess: true }, u64::MAX),
+            ]),
+        };
+        let yaml = serde_yaml::to_string(&top_score).unwrap();
+        assert_eq!(serde_yaml::from_str::<TopScore>(&yaml).unwrap(), top_score);
+    }
+
+    #[test]
+    fn old_top_score_files_still_load() {
+        let top_score = serde_yaml::from_str::<TopScore>("scores:\n  Small: 12\n  Large: 300\n").unwrap();
+        assert_eq!(top_score.scores, HashMap::from([(GameType::Small, 12), (GameType::Large, 300)]));
+    }
+}
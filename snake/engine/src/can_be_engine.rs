@@ -0,0 +1,18 @@
+use std::collections::HashMap;
+use crate::cell::Cell;
+use crate::cell_state::CellState;
+use crate::direction::Direction;
+use crate::game_state::GameState;
+use crate::game_stats::GameStats;
+
+pub trait CanBeEngine {
+    fn get_size(&self) -> (i32, i32);
+    fn get_game_stats(&self) -> GameStats;
+    fn get_board_updates(&mut self) -> HashMap<Cell,CellState>;
+    fn get_board_state(&mut self) -> HashMap<Cell,CellState>;
+    // Returns false when the change is ignored, such as turning straight back into the snake.
+    fn change_direction(&mut self, direction: Direction) -> bool;
+    // Moves the snake one cell. The first step starts the game.
+    fn step(&mut self) -> GameState;
+    fn make_clone(&self) -> Box<dyn CanBeEngine>;
+}
@@ -0,0 +1,27 @@
+use crate::direction::Direction;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub x: i32,
+    pub y: i32
+}
+
+impl Cell {
+    pub fn next(&self, direction: Direction) -> Cell {
+        let (x_offset, y_offset) = direction.get_offset();
+        Cell { x: self.x + x_offset, y: self.y + y_offset }
+    }
+}
+
+impl From<Cell> for (i32, i32) {
+    fn from(c: Cell) -> (i32, i32) {
+        let Cell {x, y} = c;
+        (x, y)
+    }
+}
+
+impl From<(i32, i32)> for Cell {
+    fn from((x, y): (i32, i32)) -> Self {
+        Cell { x, y }
+    }
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CellState {
+    Empty,
+    Head,
+    Body,
+    Food,
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CompleteState {
+    // The snake filled the whole board.
+    Win,
+    Lose,
+}
@@ -0,0 +1,24 @@
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn get_offset(&self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+
+    pub fn is_opposite(&self, other: Direction) -> bool {
+        let (x, y) = self.get_offset();
+        let (other_x, other_y) = other.get_offset();
+        x + other_x == 0 && y + other_y == 0
+    }
+}
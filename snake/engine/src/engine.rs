@@ -0,0 +1,346 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Instant;
+use crossterm::ErrorKind;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use crate::{CanBeEngine, Cell, CellState, CompleteState, Direction, GameState, GameStats};
+
+const INITIAL_LENGTH: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct Engine {
+    game_state: GameState,
+    width: i32,
+    height: i32,
+    // The head is at the front.
+    body: VecDeque<Cell>,
+    body_cells: HashSet<Cell>,
+    // The direction of the last step, which the snake is not allowed to reverse.
+    direction: Direction,
+    next_direction: Direction,
+    food: Option<Cell>,
+    growth_remaining: usize,
+    score: u64,
+    seed: u64,
+    rng: ChaCha8Rng,
+    start_instant: Option<Instant>,
+    game_complete_time_millis: u64,
+    changed_cells: HashSet<Cell>,
+}
+
+impl Engine {
+    pub fn new(width: i32, height: i32) -> Self {
+        Engine::new_with_seed(width, height, rand::thread_rng().gen())
+    }
+
+    // The same seed and the same moves will always place the food in the same cells.
+    pub fn new_with_seed(width: i32, height: i32, seed: u64) -> Self {
+        let head = Cell { x: width / 2, y: height / 2 };
+        let body: VecDeque<Cell> = (0..INITIAL_LENGTH as i32)
+            .map(|i| Cell { x: head.x - i, y: head.y })
+            .collect();
+        let mut changed_cells = HashSet::new();
+        for x in 0..width {
+            for y in 0..height {
+                changed_cells.insert(Cell { x, y });
+            }
+        }
+        let mut engine = Engine {
+            game_state: GameState::Initialised,
+            width,
+            height,
+            body_cells: body.iter().copied().collect(),
+            body,
+            direction: Direction::Right,
+            next_direction: Direction::Right,
+            food: None,
+            growth_remaining: 0,
+            score: 0,
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            start_instant: None,
+            game_complete_time_millis: 0,
+            changed_cells,
+        };
+        engine.place_food();
+        engine
+    }
+
+    // The snake starts in the middle of the board and needs room to move right.
+    pub fn validate_settings(width: i32, height: i32) -> crossterm::Result<()> {
+        let min_width = INITIAL_LENGTH as i32 + 2;
+        if width < min_width || height < 1 {
+            Err(ErrorKind::other(format!("The board must be at least {}x1", min_width)))?;
+        }
+        Ok(())
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn get_head(&self) -> Cell {
+        self.body[0]
+    }
+
+    fn is_on_board(&self, cell: Cell) -> bool {
+        cell.x >= 0 && cell.y >= 0 && cell.x < self.width && cell.y < self.height
+    }
+
+    fn get_cell_state(&self, cell: Cell) -> CellState {
+        if cell == self.get_head() {
+            CellState::Head
+        } else if self.body_cells.contains(&cell) {
+            CellState::Body
+        } else if self.food == Some(cell) {
+            CellState::Food
+        } else {
+            CellState::Empty
+        }
+    }
+
+    // Picks an empty cell for the food. Returns false when there is nowhere left to put it.
+    fn place_food(&mut self) -> bool {
+        let mut empty_cells = vec![];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let cell = Cell { x, y };
+                if !self.body_cells.contains(&cell) {
+                    empty_cells.push(cell);
+                }
+            }
+        }
+        self.food = match empty_cells.is_empty() {
+            true => None,
+            false => Some(empty_cells[self.rng.gen_range(0..empty_cells.len())]),
+        };
+        if let Some(food) = self.food {
+            self.changed_cells.insert(food);
+        }
+        self.food.is_some()
+    }
+
+    fn complete(&mut self, complete_state: CompleteState) -> GameState {
+        self.game_state = GameState::Complete(complete_state);
+        if let Some(start_instant) = self.start_instant {
+            self.game_complete_time_millis = start_instant.elapsed().as_millis() as u64;
+        }
+        self.game_state
+    }
+}
+
+impl CanBeEngine for Engine {
+    fn get_size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    fn get_game_stats(&self) -> GameStats {
+        let game_run_time_millis = match (self.game_state, self.start_instant) {
+            (GameState::Complete(_), _) => self.game_complete_time_millis,
+            (_, Some(start_instant)) => start_instant.elapsed().as_millis() as u64,
+            (_, None) => 0,
+        };
+        GameStats {
+            game_state: self.game_state,
+            score: self.score,
+            length: self.body.len(),
+            game_run_time_millis,
+        }
+    }
+
+    fn get_board_updates(&mut self) -> HashMap<Cell,CellState> {
+        let mut cell_updates = HashMap::new();
+        for cell in self.changed_cells.iter() {
+            cell_updates.insert(*cell, self.get_cell_state(*cell));
+        }
+        self.changed_cells.clear();
+        cell_updates
+    }
+
+    fn get_board_state(&mut self) -> HashMap<Cell,CellState> {
+        let mut board_state = HashMap::new();
+        for x in 0..self.width {
+            for y in 0..self.height {
+                let cell = Cell { x, y };
+                board_state.insert(cell, self.get_cell_state(cell));
+            }
+        }
+        board_state
+    }
+
+    fn change_direction(&mut self, direction: Direction) -> bool {
+        if let GameState::Complete(_) = self.game_state {
+            return false;
+        }
+        if direction.is_opposite(self.direction) {
+            return false;
+        }
+        self.next_direction = direction;
+        true
+    }
+
+    fn step(&mut self) -> GameState {
+        match self.game_state {
+            GameState::Complete(_) => return self.game_state,
+            GameState::Initialised => {
+                self.game_state = GameState::Playing;
+                self.start_instant = Some(Instant::now());
+            }
+            GameState::Playing => {}
+        }
+        self.direction = self.next_direction;
+        let head = self.get_head();
+        let new_head = head.next(self.direction);
+        if !self.is_on_board(new_head) {
+            return self.complete(CompleteState::Lose);
+        }
+
+        let ate_food = self.food == Some(new_head);
+        if ate_food {
+            self.score += 1;
+            self.growth_remaining += 1;
+            self.food = None;
+        }
+        // The tail moves out of the way in the same step, unless the snake is growing.
+        let tail = match self.growth_remaining {
+            0 => self.body.pop_back(),
+            _ => {
+                self.growth_remaining -= 1;
+                None
+            }
+        };
+        if let Some(tail) = tail {
+            self.body_cells.remove(&tail);
+            self.changed_cells.insert(tail);
+        }
+        if self.body_cells.contains(&new_head) {
+            if let Some(tail) = tail {
+                self.body.push_back(tail);
+                self.body_cells.insert(tail);
+            }
+            return self.complete(CompleteState::Lose);
+        }
+
+        self.body.push_front(new_head);
+        self.body_cells.insert(new_head);
+        self.changed_cells.insert(head);
+        self.changed_cells.insert(new_head);
+
+        if ate_food && !self.place_food() {
+            return self.complete(CompleteState::Win);
+        }
+        self.game_state
+    }
+
+    // The clone starts again from the same seed, so it places the same food.
+    fn make_clone(&self) -> Box<dyn CanBeEngine> {
+        Box::from(Engine::new_with_seed(self.width, self.height, self.seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Puts the snake on the board, head first, with the food out of the way unless it is given.
+    fn set_snake(engine: &mut Engine, body: &[(i32, i32)], direction: Direction, food: Option<(i32, i32)>) {
+        engine.body = body.iter().map(|&cell| cell.into()).collect();
+        engine.body_cells = engine.body.iter().copied().collect();
+        engine.direction = direction;
+        engine.next_direction = direction;
+        engine.food = food.map(|cell| cell.into());
+    }
+
+    #[test]
+    fn only_the_last_stepped_direction_cannot_be_reversed() {
+        let mut engine = Engine::new_with_seed(10, 10, 1);
+        assert!(!engine.change_direction(Direction::Left));
+        // Up and then down before the next step only turns the snake down.
+        assert!(engine.change_direction(Direction::Up));
+        assert!(engine.change_direction(Direction::Down));
+        assert_eq!(engine.step(), GameState::Playing);
+        assert_eq!(engine.get_head(), Cell { x: 5, y: 6 });
+        assert!(!engine.change_direction(Direction::Up));
+        assert!(engine.change_direction(Direction::Left));
+    }
+
+    #[test]
+    fn eating_grows_the_snake_and_places_new_food() {
+        let mut engine = Engine::new_with_seed(10, 10, 1);
+        set_snake(&mut engine, &[(5, 5), (4, 5), (3, 5)], Direction::Right, Some((6, 5)));
+        engine.step();
+        let game_stats = engine.get_game_stats();
+        assert_eq!((game_stats.score, game_stats.length), (1, 4));
+        assert_eq!(engine.body.back(), Some(&Cell { x: 3, y: 5 }));
+        let food = engine.food.unwrap();
+        assert!(!engine.body_cells.contains(&food));
+        // Without food the length stays the same.
+        set_snake(&mut engine, &[(5, 5), (4, 5), (3, 5)], Direction::Right, Some((0, 0)));
+        engine.step();
+        assert_eq!(engine.get_game_stats().length, 3);
+    }
+
+    #[test]
+    fn moving_off_the_board_loses() {
+        let mut engine = Engine::new_with_seed(5, 1, 1);
+        set_snake(&mut engine, &[(3, 0), (2, 0), (1, 0)], Direction::Right, Some((0, 0)));
+        assert_eq!(engine.step(), GameState::Playing);
+        assert_eq!(engine.step(), GameState::Complete(CompleteState::Lose));
+        // The snake stays where it was.
+        assert_eq!(engine.get_head(), Cell { x: 4, y: 0 });
+        assert!(!engine.change_direction(Direction::Up));
+    }
+
+    #[test]
+    fn moving_into_the_body_loses() {
+        let mut engine = Engine::new_with_seed(10, 10, 1);
+        set_snake(&mut engine, &[(1, 1), (2, 1), (2, 2), (1, 2), (0, 2)], Direction::Left, None);
+        engine.change_direction(Direction::Down);
+        assert_eq!(engine.step(), GameState::Complete(CompleteState::Lose));
+        assert_eq!(engine.get_game_stats().length, 5);
+    }
+
+    #[test]
+    fn cell_the_tail_leaves_can_be_moved_into() {
+        let mut engine = Engine::new_with_seed(10, 10, 1);
+        set_snake(&mut engine, &[(1, 1), (2, 1), (2, 2), (1, 2)], Direction::Left, None);
+        engine.change_direction(Direction::Down);
+        assert_eq!(engine.step(), GameState::Playing);
+        assert_eq!(engine.get_head(), Cell { x: 1, y: 2 });
+        // Unless the snake is still growing, so the tail stays put.
+        set_snake(&mut engine, &[(1, 1), (2, 1), (2, 2), (1, 2)], Direction::Left, None);
+        engine.growth_remaining = 1;
+        engine.change_direction(Direction::Down);
+        assert_eq!(engine.step(), GameState::Complete(CompleteState::Lose));
+        assert_eq!(engine.body.back(), Some(&Cell { x: 1, y: 2 }));
+    }
+
+    #[test]
+    fn same_seed_places_the_same_food() {
+        let mut engine = Engine::new_with_seed(20, 20, 42);
+        let mut other_engine = Engine::new_with_seed(20, 20, 42);
+        let clone_food = engine.make_clone().get_board_state().into_iter().find(|(_, state)| *state == CellState::Food);
+        assert_eq!(clone_food.map(|(cell, _)| cell), engine.food);
+        for _ in 0..5 {
+            assert_eq!(engine.food, other_engine.food);
+            // Eat the food straight away, wherever it is.
+            let food: (i32, i32) = engine.food.unwrap().into();
+            let head = (food.0 - 1, food.1);
+            set_snake(&mut engine, &[head, (head.0 - 1, head.1), (head.0 - 2, head.1)], Direction::Right, Some(food));
+            set_snake(&mut other_engine, &[head, (head.0 - 1, head.1), (head.0 - 2, head.1)], Direction::Right, Some(food));
+            engine.step();
+            other_engine.step();
+        }
+        assert_eq!(engine.get_game_stats().score, 5);
+    }
+
+    #[test]
+    fn filling_the_board_wins() {
+        let mut engine = Engine::new_with_seed(5, 1, 1);
+        set_snake(&mut engine, &[(3, 0), (2, 0), (1, 0), (0, 0)], Direction::Right, Some((4, 0)));
+        assert_eq!(engine.step(), GameState::Complete(CompleteState::Win));
+        assert_eq!(engine.food, None);
+        assert_eq!(engine.get_game_stats().length, 5);
+        assert_eq!(engine.step(), GameState::Complete(CompleteState::Win));
+    }
+}
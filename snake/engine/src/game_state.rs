@@ -0,0 +1,9 @@
+use crate::CompleteState;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum GameState {
+    #[default]
+    Initialised,
+    Playing,
+    Complete(CompleteState),
+}
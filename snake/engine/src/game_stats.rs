@@ -0,0 +1,9 @@
+use crate::GameState;
+
+#[derive(Debug, Copy, Clone)]
+pub struct GameStats {
+    pub game_state: GameState,
+    pub score: u64,
+    pub length: usize,
+    pub game_run_time_millis: u64,
+}
@@ -0,0 +1,16 @@
+use cell::Cell;
+use cell_state::CellState;
+use complete_state::CompleteState;
+use direction::Direction;
+use game_state::GameState;
+use game_stats::GameStats;
+use crate::can_be_engine::CanBeEngine;
+
+pub mod can_be_engine;
+pub mod cell;
+pub mod cell_state;
+pub mod complete_state;
+pub mod direction;
+pub mod game_state;
+pub mod game_stats;
+pub mod engine;
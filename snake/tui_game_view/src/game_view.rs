@@ -0,0 +1,350 @@
+use crate::snake_size::SnakeSize;
+use crossterm::Result;
+use game_actions::click_action::ClickAction;
+use game_actions::click_action::ClickAction::Refresh;
+use game_actions::config::snake_config::SnakeConfig;
+use game_actions::game_context::GameContext;
+use snake_engine::can_be_engine::CanBeEngine;
+use snake_engine::cell::Cell;
+use snake_engine::cell_state::CellState;
+use snake_engine::complete_state::CompleteState;
+use snake_engine::direction::Direction;
+use snake_engine::engine::Engine;
+use snake_engine::game_state::GameState::{Complete, Initialised, Playing};
+use snake_engine::game_stats::GameStats;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tui::screen::dimension::Dimension;
+use tui::screen::point::Point;
+use tui::screen::style::Style;
+use tui::screen::theme::Theme;
+use tui::screen::window::component::Component;
+use tui::screen::window::key_action::KeyAction;
+use tui::screen::window::mouse_action::MouseAction;
+use tui::screen::window::update_element::UpdateElement;
+use uuid::Uuid;
+
+pub const MIN_WIDTH: i32 = 12;
+pub const MAX_WIDTH: i32 = 60;
+pub const MAX_HEIGHT: i32 = 40;
+
+pub struct GameView {
+    id: Uuid,
+    engine: Box<dyn CanBeEngine>,
+    engine_size: Dimension,
+    last_step: Instant,
+    retry_button_location: Vec<Point>,
+    config: SnakeConfig,
+    theme: Theme,
+}
+
+fn convert_to_wide_char(c: char) -> char {
+    char::from_u32(c as u32 + 0xFEE0).unwrap()
+}
+
+impl GameView {
+    pub fn new(size: SnakeSize) -> GameView {
+        GameView::new_with_context(size, &GameContext::default())
+    }
+
+    pub fn new_with_context(size: SnakeSize, context: &GameContext) -> GameView {
+        let (width, height) = size.get_board_size();
+        let engine = match context.seed {
+            Some(seed) => Engine::new_with_seed(width, height, seed),
+            None => Engine::new(width, height),
+        };
+        let engine_size: Dimension = engine.get_size().into();
+        GameView {
+            id: Uuid::new_v4(),
+            engine: Box::from(engine),
+            engine_size,
+            last_step: Instant::now(),
+            retry_button_location: vec![],
+            config: context.config.snake.clone(),
+            theme: Theme::default(),
+        }
+    }
+
+    // The stats line needs 24 columns, which is 12 cells, and the board has to fit in a terminal.
+    pub fn validate_size(size: SnakeSize) -> Result<()> {
+        let (width, height) = size.get_board_size();
+        if !(MIN_WIDTH..=MAX_WIDTH).contains(&width) {
+            Err(crossterm::ErrorKind::other(format!("Width must be {}-{}", MIN_WIDTH, MAX_WIDTH)))?;
+        }
+        if height > MAX_HEIGHT {
+            Err(crossterm::ErrorKind::other(format!("Height must be at most {}", MAX_HEIGHT)))?;
+        }
+        Engine::validate_settings(width, height)
+    }
+
+    fn reset(&mut self) {
+        self.engine = self.engine.make_clone();
+    }
+
+    // The snake speeds up as it eats.
+    fn get_step_interval(&self) -> Duration {
+        let score = self.engine.get_game_stats().score;
+        let millis = self.config.start_step_millis.saturating_sub(score * self.config.step_millis_per_food);
+        Duration::from_millis(millis.max(self.config.min_step_millis))
+    }
+
+    // Plays every step that is due since the last one.
+    fn advance(&mut self, now: Instant) {
+        if self.engine.get_game_stats().game_state != Playing {
+            return;
+        }
+        while now.saturating_duration_since(self.last_step) >= self.get_step_interval() {
+            self.last_step += self.get_step_interval();
+            if let Complete(_) = self.engine.step() {
+                return;
+            }
+        }
+    }
+
+    fn turn(&mut self, direction: Direction) {
+        self.engine.change_direction(direction);
+        if self.engine.get_game_stats().game_state == Initialised {
+            self.engine.step();
+            self.last_step = Instant::now();
+        }
+    }
+
+    fn convert_engine_size_to_size(&self) -> Dimension {
+        let engine_size = self.engine_size;
+        let y_offset = if let Complete(_) = self.engine.get_game_stats().game_state {
+            4
+        } else {
+            2
+        };
+        (engine_size.width * 2, engine_size.height + y_offset).into()
+    }
+
+    fn push_stat(updates: &mut Vec<UpdateElement>, point: Point, icon: char, value: u64) {
+        updates.push(UpdateElement {
+            point,
+            value: icon.to_string(),
+            style: Style::default(),
+        });
+        for (i, char) in format!("{:03}", value.min(999)).chars().enumerate() {
+            updates.push(UpdateElement {
+                point: point + (((i + 1) as i32) * 2, 0).into(),
+                value: convert_to_wide_char(char).to_string(),
+                style: Style::default(),
+            });
+        }
+    }
+
+    fn get_stats_board_updates(&self, game_stats: GameStats, prior_updates: &mut Vec<UpdateElement>) {
+        let size = self.get_size();
+        for x in 0..size.width {
+            prior_updates.push(UpdateElement {
+                point: (x, 0).into(),
+                value: ' '.to_string(),
+                style: Style::default(),
+            });
+            // draw separator
+            prior_updates.push(UpdateElement {
+                point: (x, 1).into(),
+                value: '━'.to_string(),
+                style: Style::default(),
+            });
+        }
+        // Each stat is an icon and three wide digits, so 8 columns.
+        let gap = (size.width - 24) / 2;
+        let glyphs = &self.config.glyphs;
+        GameView::push_stat(prior_updates, (0, 0).into(), glyphs.food, game_stats.score);
+        GameView::push_stat(prior_updates, (8 + gap, 0).into(), glyphs.length, game_stats.length as u64);
+        GameView::push_stat(prior_updates, (size.width - 8, 0).into(), glyphs.clock, game_stats.game_run_time_millis / 1000);
+    }
+
+    fn append_updates_from_engine(&self, game_updates: &HashMap<Cell, CellState>, prior_updates: &mut Vec<UpdateElement>) {
+        let glyphs = &self.config.glyphs;
+        for (cell, cell_state) in game_updates.iter() {
+            let value = match cell_state {
+                CellState::Empty => glyphs.empty,
+                CellState::Head => glyphs.head,
+                CellState::Body => glyphs.body,
+                CellState::Food => glyphs.food,
+            };
+            prior_updates.push(UpdateElement {
+                point: (cell.x * 2, cell.y + 2).into(),
+                value: value.to_string(),
+                style: Style::default(),
+            });
+        }
+    }
+
+    fn append_complete_menu_updates(&mut self, complete_state: CompleteState, updates: &mut Vec<UpdateElement>) {
+        let size = self.get_size();
+        for x in 0..size.width {
+            // draw separator
+            updates.push(UpdateElement {
+                point: (x, size.height - 2).into(),
+                value: '━'.to_string(),
+                style: Style::default(),
+            });
+        }
+        let halfway_point: Point = ((size.width / 2) - 1, size.height - 1).into();
+        updates.push(UpdateElement {
+            point: halfway_point,
+            value: '┃'.to_string(),
+            style: Style::default(),
+        });
+        let (message, style) = match complete_state {
+            CompleteState::Win => ("You win!", self.theme.win),
+            CompleteState::Lose => ("Game over", self.theme.lose),
+        };
+        let message_x = (halfway_point.x - message.len() as i32) / 2;
+        for (i, char) in message.chars().enumerate() {
+            updates.push(UpdateElement {
+                point: (message_x + i as i32, halfway_point.y).into(),
+                value: char.to_string(),
+                style,
+            });
+        }
+        let retry_point: Point = (halfway_point.x + (size.width - halfway_point.x - 6) / 2, halfway_point.y).into();
+        for (i, char) in "Retry?".chars().enumerate() {
+            updates.push(UpdateElement {
+                point: retry_point + (i as i32, 0).into(),
+                value: char.to_string(),
+                style: Style::default(),
+            });
+        }
+        self.retry_button_location.clear();
+        for i in halfway_point.x + 1..size.width {
+            self.retry_button_location.push((i, halfway_point.y).into());
+        }
+    }
+
+    fn get_update_elements(
+        &mut self,
+        board_state_getter: fn(&mut Box<dyn CanBeEngine>) -> HashMap<Cell, CellState>,
+    ) -> Result<Vec<UpdateElement>> {
+        let mut updates = vec![];
+        let game_updates = board_state_getter(&mut self.engine);
+        let game_stats = self.engine.get_game_stats();
+        self.get_stats_board_updates(game_stats, &mut updates);
+        self.append_updates_from_engine(&game_updates, &mut updates);
+        if let Complete(complete_state) = game_stats.game_state {
+            self.append_complete_menu_updates(complete_state, &mut updates);
+        } else {
+            self.retry_button_location.clear();
+        }
+        Ok(updates)
+    }
+}
+
+impl Component<ClickAction> for GameView {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        self.convert_engine_size_to_size()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        self.get_update_elements(|e| e.get_board_state())
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        self.get_update_elements(|e| e.get_board_updates())
+    }
+
+    fn handle_click(&mut self, click: MouseAction) -> Result<Vec<ClickAction>> {
+        if let MouseAction::Left(p) = click {
+            if self.retry_button_location.contains(&p) {
+                self.reset();
+                return Ok(vec![Refresh]);
+            }
+        }
+        Ok(vec![])
+    }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<ClickAction>>> {
+        let keys = &self.config.keys;
+        let direction = match key {
+            k if keys.up.contains(&k) => Direction::Up,
+            k if keys.down.contains(&k) => Direction::Down,
+            k if keys.left.contains(&k) => Direction::Left,
+            k if keys.right.contains(&k) => Direction::Right,
+            k if keys.retry.contains(&k) && !self.retry_button_location.is_empty() => {
+                self.reset();
+                return Ok(Some(vec![Refresh]));
+            }
+            _ => return Ok(None),
+        };
+        self.turn(direction);
+        Ok(Some(vec![]))
+    }
+
+    fn get_time_until_tick(&self, now: Instant) -> Option<Duration> {
+        match self.engine.get_game_stats().game_state {
+            Playing => Some((self.last_step + self.get_step_interval()).saturating_duration_since(now)),
+            _ => None,
+        }
+    }
+
+    fn on_tick(&mut self, now: Instant) -> Result<Vec<ClickAction>> {
+        self.advance(now);
+        Ok(vec![])
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The value drawn last at each column of a row. The header is cleared first, so wide glyphs
+    // are followed by the space under their right half.
+    fn get_row(view: &mut GameView, y: i32) -> String {
+        let mut row: Vec<(i32, String)> = view.get_state().unwrap().into_iter()
+            .filter(|update| update.point.y == y)
+            .map(|update| (update.point.x, update.value))
+            .collect();
+        // Later updates draw over earlier ones.
+        row.reverse();
+        row.sort_by_key(|(x, _)| *x);
+        row.dedup_by_key(|(x, _)| *x);
+        row.into_iter().map(|(_, value)| value).collect()
+    }
+
+    #[test]
+    fn named_sizes_are_valid() {
+        for size in [SnakeSize::Small, SnakeSize::Medium, SnakeSize::Large] {
+            assert!(GameView::validate_size(size).is_ok());
+        }
+        assert!(GameView::validate_size(SnakeSize::Custom { width: MAX_WIDTH, height: MAX_HEIGHT }).is_ok());
+    }
+
+    #[test]
+    fn boards_too_small_or_too_big_are_rejected() {
+        let error = |width, height| GameView::validate_size(SnakeSize::Custom { width, height }).unwrap_err().to_string();
+        assert_eq!(error(MIN_WIDTH - 1, 10), "Width must be 12-60");
+        assert_eq!(error(MAX_WIDTH + 1, 10), "Width must be 12-60");
+        assert_eq!(error(20, MAX_HEIGHT + 1), "Height must be at most 40");
+        assert!(GameView::validate_size(SnakeSize::Custom { width: 20, height: 0 }).is_err());
+    }
+
+    #[test]
+    fn header_shows_the_score_length_and_time() {
+        let context = GameContext { seed: Some(1), ..GameContext::default() };
+        let mut view = GameView::new_with_context(SnakeSize::Small, &context);
+        // 28 columns leave a gap of 2 on each side of the length.
+        assert_eq!(get_row(&mut view, 0), "🍎 ０ ０ ０   🐍 ０ ０ ３   🕑 ０ ０ ０ ");
+        assert_eq!(get_row(&mut view, 1), "━".repeat(28));
+    }
+
+    #[test]
+    fn header_icons_come_from_the_config() {
+        let mut context = GameContext { seed: Some(1), ..GameContext::default() };
+        context.config.snake.glyphs.length = '🐉';
+        context.config.snake.glyphs.clock = '⏳';
+        let mut view = GameView::new_with_context(SnakeSize::Small, &context);
+        assert_eq!(get_row(&mut view, 0), "🍎 ０ ０ ０   🐉 ０ ０ ３   ⏳ ０ ０ ０ ");
+    }
+}
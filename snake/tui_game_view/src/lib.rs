@@ -0,0 +1,3 @@
+pub mod game_view;
+pub mod snake_descriptor;
+pub mod snake_size;
@@ -0,0 +1,57 @@
+use crate::game_view::GameView;
+use crate::snake_size::SnakeSize;
+use crossterm::{ErrorKind, Result};
+use game_actions::click_action::ClickAction;
+use game_actions::game_context::GameContext;
+use game_actions::game_descriptor::GameDescriptor;
+use game_actions::game_variant::GameVariant;
+use game_actions::start_options::StartOptions;
+use tui::screen::window::component::Component;
+
+pub const SNAKE_ID: &str = "snake";
+
+pub struct SnakeDescriptor;
+
+impl GameDescriptor for SnakeDescriptor {
+    fn get_id(&self) -> &'static str {
+        SNAKE_ID
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Snake"
+    }
+
+    fn get_variants(&self) -> Vec<GameVariant> {
+        vec![SnakeSize::Small.into(), SnakeSize::Medium.into(), SnakeSize::Large.into()]
+    }
+
+    fn get_variant_label(&self, variant: &GameVariant) -> String {
+        match SnakeSize::parse(variant) {
+            Ok(size) => size.get_label(),
+            Err(_) => variant.get_id().to_string(),
+        }
+    }
+
+    fn get_window_title(&self, variant: &GameVariant) -> String {
+        format!("Snake {}", self.get_variant_label(variant))
+    }
+
+    fn get_start_variant(&self, options: &StartOptions, context: &GameContext) -> std::result::Result<GameVariant, String> {
+        if options.mines.is_some() {
+            return Err("Snake has no mines, so --mines cannot be used with it".to_string());
+        }
+        let size = match (options.size, &options.difficulty) {
+            (Some((width, height)), _) => SnakeSize::Custom { width, height },
+            (None, Some(difficulty)) => SnakeSize::from_difficulty(difficulty)?,
+            (None, None) => SnakeSize::from_difficulty(&context.config.default_difficulty)
+                .map_err(|e| format!("default_difficulty: {}", e))?,
+        };
+        Ok(size.into())
+    }
+
+    fn create(&self, variant: &GameVariant, context: &GameContext) -> Result<Box<dyn Component<ClickAction>>> {
+        let size = SnakeSize::parse(variant).map_err(ErrorKind::other)?;
+        GameView::validate_size(size)?;
+        Ok(Box::from(GameView::new_with_context(size, context)))
+    }
+}
@@ -0,0 +1,85 @@
+use game_actions::game_variant::GameVariant;
+
+// The board sizes snake is played on, kept in a GameVariant by their id.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SnakeSize {
+    Small,
+    Medium,
+    Large,
+    Custom { width: i32, height: i32 },
+}
+
+impl SnakeSize {
+    // Easy and hard are accepted too, as the minesweeper names of the difficulties.
+    pub fn from_difficulty(value: &str) -> Result<SnakeSize, String> {
+        match value.to_lowercase().as_str() {
+            "small" | "easy" => Ok(SnakeSize::Small),
+            "medium" => Ok(SnakeSize::Medium),
+            "large" | "hard" => Ok(SnakeSize::Large),
+            _ => Err(format!("Unknown size {}, expected small, medium or large", value)),
+        }
+    }
+
+    // Small, medium, large or a custom size such as 20x10.
+    pub fn parse(variant: &GameVariant) -> Result<SnakeSize, String> {
+        let invalid = || format!("Invalid snake size {}", variant.get_id());
+        match variant.get_id() {
+            "small" => Ok(SnakeSize::Small),
+            "medium" => Ok(SnakeSize::Medium),
+            "large" => Ok(SnakeSize::Large),
+            size => {
+                let (width, height) = size.split_once('x').ok_or_else(invalid)?;
+                Ok(SnakeSize::Custom {
+                    width: width.parse().map_err(|_| invalid())?,
+                    height: height.parse().map_err(|_| invalid())?,
+                })
+            }
+        }
+    }
+
+    pub fn get_label(self) -> String {
+        match self {
+            SnakeSize::Small => "Small".to_string(),
+            SnakeSize::Medium => "Medium".to_string(),
+            SnakeSize::Large => "Large".to_string(),
+            SnakeSize::Custom { width, height } => format!("{}x{}", width, height),
+        }
+    }
+
+    pub fn get_board_size(self) -> (i32, i32) {
+        match self {
+            SnakeSize::Small => (14, 8),
+            SnakeSize::Medium => (20, 12),
+            SnakeSize::Large => (28, 16),
+            SnakeSize::Custom { width, height } => (width, height),
+        }
+    }
+}
+
+impl From<SnakeSize> for GameVariant {
+    fn from(size: SnakeSize) -> Self {
+        match size {
+            SnakeSize::Custom { width, height } => GameVariant::new(format!("{}x{}", width, height)),
+            _ => GameVariant::new(size.get_label().to_lowercase()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_size_round_trips_through_its_id() {
+        for size in [SnakeSize::Small, SnakeSize::Medium, SnakeSize::Large, SnakeSize::Custom { width: 20, height: 10 }] {
+            assert_eq!(SnakeSize::parse(&size.into()), Ok(size));
+        }
+    }
+
+    #[test]
+    fn minesweeper_difficulties_pick_a_size() {
+        assert_eq!(SnakeSize::from_difficulty("Easy"), Ok(SnakeSize::Small));
+        assert_eq!(SnakeSize::from_difficulty("hard"), Ok(SnakeSize::Large));
+        assert!(SnakeSize::from_difficulty("huge").is_err());
+    }
+}
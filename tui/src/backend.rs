@@ -0,0 +1,15 @@
+pub mod crossterm_backend;
+pub mod test_backend;
+
+use crossterm::Result;
+use crossterm::style::StyledContent;
+
+// Everything Screen needs from a terminal.
+pub trait Backend {
+    fn move_to(&mut self, x: i32, y: i32) -> Result<()>;
+    fn print(&mut self, content: StyledContent<String>) -> Result<()>;
+    fn clear(&mut self) -> Result<()>;
+    fn hide_cursor(&mut self) -> Result<()>;
+    fn size(&self) -> Result<(i32, i32)>;
+    fn flush(&mut self) -> Result<()>;
+}
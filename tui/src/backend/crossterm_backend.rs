@@ -0,0 +1,48 @@
+use std::io::{stdout, Stdout, Write};
+use crossterm::{cursor, queue, Result, style, terminal};
+use crossterm::style::StyledContent;
+use crate::backend::Backend;
+
+// Queues commands to stdout until flushed.
+pub struct CrosstermBackend {
+    stdout: Stdout,
+}
+
+impl CrosstermBackend {
+    pub fn new() -> Self {
+        CrosstermBackend { stdout: stdout() }
+    }
+}
+
+impl Default for CrosstermBackend {
+    fn default() -> Self {
+        CrosstermBackend::new()
+    }
+}
+
+impl Backend for CrosstermBackend {
+    fn move_to(&mut self, x: i32, y: i32) -> Result<()> {
+        queue!(self.stdout, cursor::MoveTo(x as u16, y as u16))
+    }
+
+    fn print(&mut self, content: StyledContent<String>) -> Result<()> {
+        queue!(self.stdout, style::PrintStyledContent(content))
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        queue!(self.stdout, terminal::Clear(terminal::ClearType::All))
+    }
+
+    fn hide_cursor(&mut self) -> Result<()> {
+        queue!(self.stdout, cursor::Hide)
+    }
+
+    fn size(&self) -> Result<(i32, i32)> {
+        let (width, height) = terminal::size()?;
+        Ok((width as i32, height as i32))
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.stdout.flush()
+    }
+}
@@ -0,0 +1,125 @@
+use crossterm::Result;
+use crossterm::style::StyledContent;
+use unicode_segmentation::UnicodeSegmentation;
+use crate::backend::Backend;
+use crate::screen::cell_grid::CellGrid;
+use crate::screen::point::Point;
+use crate::screen::screen_cell::ScreenCell;
+use crate::screen::style::Style;
+use crate::screen::text;
+
+// Keeps the screen in memory so what was drawn can be checked without a terminal.
+pub struct TestBackend {
+    cells: CellGrid,
+    cursor: Point,
+    flush_count: usize,
+}
+
+impl TestBackend {
+    pub fn new(width: i32, height: i32) -> Self {
+        TestBackend {
+            cells: CellGrid::new(width, height),
+            cursor: (0, 0).into(),
+            flush_count: 0,
+        }
+    }
+
+    pub fn get_cell(&self, x: i32, y: i32) -> &ScreenCell {
+        self.cells.get(x, y)
+    }
+
+    // The text of a row, with wide graphemes counted once.
+    pub fn get_line(&self, y: i32) -> String {
+        (0..self.cells.get_width())
+            .filter(|x| !self.cells.is_continuation(*x, y))
+            .map(|x| self.cells.get(x, y).value.as_str())
+            .collect()
+    }
+
+    pub fn get_lines(&self) -> Vec<String> {
+        (0..self.cells.get_height()).map(|y| self.get_line(y)).collect()
+    }
+
+    pub fn get_flush_count(&self) -> usize {
+        self.flush_count
+    }
+
+    // Like a terminal, writing over either half of a wide grapheme blanks the other half.
+    fn set_cell(&mut self, point: Point, cell: ScreenCell) {
+        let (x, y) = point.into();
+        if self.cells.is_continuation(x, y) {
+            self.blank_cell((x - 1, y).into());
+        }
+        self.clear_wide_tail(point);
+        self.cells.set(point, cell);
+    }
+
+    fn clear_wide_tail(&mut self, point: Point) {
+        let (x, y) = point.into();
+        if self.cells.get(x, y).is_wide() && self.cells.is_continuation(x + 1, y) {
+            self.blank_cell((x + 1, y).into());
+        }
+    }
+
+    fn blank_cell(&mut self, point: Point) {
+        let style = self.cells.get(point.x, point.y).style;
+        self.cells.set(point, ScreenCell { value: " ".to_string(), style });
+    }
+
+    // Like a terminal, the content is lost when the size changes.
+    pub fn resize(&mut self, width: i32, height: i32) {
+        self.cells = CellGrid::new(width, height);
+    }
+}
+
+impl Backend for TestBackend {
+    fn move_to(&mut self, x: i32, y: i32) -> Result<()> {
+        self.cursor = (x, y).into();
+        Ok(())
+    }
+
+    fn print(&mut self, content: StyledContent<String>) -> Result<()> {
+        let style = content.style();
+        for grapheme in content.content().graphemes(true) {
+            let width = text::get_width(grapheme);
+            if width == 0 {
+                continue;
+            }
+            let cell = ScreenCell {
+                value: grapheme.to_string(),
+                style: Style {
+                    fg: style.foreground_color,
+                    bg: style.background_color,
+                    attributes: style.attributes,
+                },
+            };
+            let placeholder = cell.placeholder();
+            self.set_cell(self.cursor, cell);
+            if width == 2 {
+                let placeholder_point = self.cursor + (1, 0).into();
+                self.clear_wide_tail(placeholder_point);
+                self.cells.set(placeholder_point, placeholder);
+            }
+            self.cursor.x += width;
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.cells.clear();
+        Ok(())
+    }
+
+    fn hide_cursor(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> Result<(i32, i32)> {
+        Ok((self.cells.get_width(), self.cells.get_height()))
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.flush_count += 1;
+        Ok(())
+    }
+}
@@ -1 +1,4 @@
+pub mod backend;
 pub mod screen;
+#[cfg(test)]
+mod test_support;
@@ -1,29 +1,74 @@
 pub mod window;
 pub mod dimension;
 pub mod point;
+pub mod cell_grid;
+pub mod screen_cell;
+pub mod style;
+pub mod text;
+pub mod theme;
 
 use std::cmp::Ordering;
-use crossterm::{cursor, ErrorKind, queue, Result, style::{self, Color, StyledContent, Stylize}, terminal};
-use std::collections::{HashMap, HashSet};
-use std::io;
-use std::io::{stdout, Stdout, Write};
+use crossterm::{ErrorKind, Result, style::{ContentStyle, StyledContent}};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 use window::Window;
+use crate::backend::Backend;
+use crate::backend::crossterm_backend::CrosstermBackend;
+use crate::screen::cell_grid::CellGrid;
 use crate::screen::point::Point;
+use crate::screen::screen_cell::ScreenCell;
+use crate::screen::style::Style;
+use crate::screen::theme::Theme;
 use crate::screen::window::component::Component;
 use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::key_action::KeyAction;
 use crate::screen::window::mouse_action::MouseAction;
+use crate::screen::window::update_element::UpdateElement;
 
-pub struct Screen<T: HasCloseAndRefreshActions + PartialEq + Clone> {
+pub struct Screen<T: HasCloseAndRefreshActions + PartialEq + Clone, B: Backend = CrosstermBackend> {
+    backend: B,
     width: i32,
     height: i32,
     windows: Vec<Window<T>>,
-    buffer: HashMap<Uuid, HashMap<Point, StyledContent<String>>>
+    // Cells drawn by each window, relative to the window's location.
+    buffer: HashMap<Uuid, HashMap<Point, ScreenCell>>,
+    // What should be on the terminal, composited from every window by z-order.
+    back: CellGrid,
+    // What is on the terminal now.
+    front: CellGrid,
+    // Set when the back grid has to be composited again.
+    dirty: bool,
+    // Window that receives key presses. When None, or the window is gone, the top-most window has focus.
+    focused: Option<Uuid>,
+    theme: Theme,
 }
 
 impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Screen<T> {
     pub fn new(width: i32, height: i32) -> Self{
-        Screen {windows: vec![], buffer: HashMap::new(), width, height}
+        Screen::new_with_backend_and_size(CrosstermBackend::new(), width, height)
+    }
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone, B: Backend> Screen<T, B> {
+    pub fn new_with_backend(backend: B) -> Result<Self> {
+        let (width, height) = backend.size()?;
+        Ok(Screen::new_with_backend_and_size(backend, width, height))
+    }
+
+    fn new_with_backend_and_size(backend: B, width: i32, height: i32) -> Self {
+        Screen {
+            backend,
+            windows: vec![],
+            buffer: HashMap::new(),
+            back: CellGrid::new(width, height),
+            front: CellGrid::new(width, height),
+            dirty: true,
+            width,
+            height,
+            focused: None,
+            theme: Theme::default(),
+        }
     }
 
     // Gets the top-most window for a specific point.
@@ -31,11 +76,15 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Screen<T> {
         let (x,y) = click.to_point().into();
         let some_window = self.windows.iter_mut().enumerate().find(|(_,w)| {
             let size = w.get_size();
-            return x >= w.location.x && x < w.location.x + size.width + 1 &&
-                y >= w.location.y && y < w.location.y + size.height + 1;
+            x >= w.location.x && x < w.location.x + size.width + 1 &&
+                y >= w.location.y && y < w.location.y + size.height + 1
         });
         if let Some((idx, window)) = some_window {
+            if let MouseAction::Left(_) = click {
+                self.focused = Some(window.id);
+            }
             if !window.can_move || window.z == 0 {
+                let window = &mut self.windows[idx];
                 let mut p = click.to_point();
                 p.x -= window.location.x;
                 p.y -= window.location.y;
@@ -56,7 +105,7 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Screen<T> {
                     self.shuffle_windows_back_from_z(0, 0);
                     self.windows[idx].z = 0;
                     self.windows.sort_by(|w1,w2| {
-                        return if w1.z > w2.z {
+                        if w1.z > w2.z {
                             Ordering::Greater
                         } else if w1.z < w2.z {
                             Ordering::Less
@@ -64,56 +113,131 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Screen<T> {
                             Ordering::Equal
                         }
                     });
+                    self.update_focus();
                     self.refresh()?;
                 }
             }
         }
-        return Ok(vec![]);
+        Ok(vec![])
+    }
+
+    // Tab and Shift-Tab move the focus, other keys go to the focused window.
+    // Returns None when the focused window does not use the key.
+    pub fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<T>>> {
+        match key {
+            KeyAction::Tab => {
+                self.cycle_focus(1);
+                Ok(Some(vec![]))
+            }
+            KeyAction::BackTab => {
+                self.cycle_focus(-1);
+                Ok(Some(vec![]))
+            }
+            _ => match self.get_focused_index() {
+                Some(idx) => self.windows[idx].handle_key(key),
+                None => Ok(None),
+            },
+        }
+    }
+
+    // Calls on_tick on every window that is due one.
+    pub fn tick(&mut self, now: Instant) -> Result<Vec<T>> {
+        let mut actions = vec![];
+        for window in self.windows.iter_mut() {
+            if window.get_time_until_tick(now) == Some(Duration::ZERO) {
+                actions.append(&mut window.on_tick(now)?);
+            }
+        }
+        Ok(actions)
+    }
+
+    // How long until any window is due a tick, or None when no window needs one.
+    pub fn get_time_until_tick(&self, now: Instant) -> Option<Duration> {
+        self.windows.iter().filter_map(|w| w.get_time_until_tick(now)).min()
+    }
+
+    pub fn get_focused(&self) -> Option<Uuid> {
+        self.get_focused_index().map(|idx| self.windows[idx].id)
+    }
+
+    pub fn set_focused(&mut self, window_id: Uuid) {
+        self.focused = Some(window_id);
+        self.update_focus();
+    }
+
+    fn get_focused_index(&self) -> Option<usize> {
+        if self.windows.is_empty() {
+            return None;
+        }
+        let some_idx = self.focused.and_then(|id| self.windows.iter().position(|w| w.id == id));
+        Some(some_idx.unwrap_or(0))
+    }
+
+    // Moves the focus through the windows from top to bottom, wrapping around at either end.
+    fn cycle_focus(&mut self, step: i32) {
+        if let Some(idx) = self.get_focused_index() {
+            let count = self.windows.len() as i32;
+            let next_idx = (idx as i32 + step).rem_euclid(count) as usize;
+            self.focused = Some(self.windows[next_idx].id);
+            self.update_focus();
+        }
     }
 
+    fn update_focus(&mut self) {
+        let focused_idx = self.get_focused_index();
+        for (idx, window) in self.windows.iter_mut().enumerate() {
+            window.set_focused(Some(idx) == focused_idx);
+        }
+    }
+
+    pub fn get_theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    // Every window is drawn again, as the buffered cells already have the old styles.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        for window in self.windows.iter_mut() {
+            window.set_theme(&theme);
+        }
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    pub fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+
+    // The terminal is cleared after a resize, so everything is drawn again.
     pub fn change_size(&mut self, width: i32, height: i32) -> Result<()>{
         self.width = width;
         self.height = height;
+        self.back = CellGrid::new(width, height);
+        self.front = CellGrid::new(width, height);
+        self.backend.clear()?;
         self.refresh()
     }
 
-    // When no updates have happened but a window has been removed or the terminal has been resized.
+    // When no updates have happened but a window has been moved, added or removed.
     pub fn refresh(&mut self) -> Result<()> {
-        let mut point_map = HashSet::new();
-        let mut stdout = stdout();
-        queue!(stdout, terminal::Clear(terminal::ClearType::All))?;
-        for window in self.windows.iter() {
-            let some_buffer = self.buffer.get(&window.id);
-            let buffer = match some_buffer {
-                Some(b) => b,
-                None => Err(ErrorKind::new(io::ErrorKind::Other, "Should always be Some here!"))?
-            };
-            for (point, value) in buffer.iter() {
-                Screen::<T>::draw_value(&mut stdout, &mut point_map, *point, value.clone())?;
-            }
-        }
-        queue!(stdout,cursor::Hide)?;
-        stdout.flush()?;
-        Ok(())
+        self.dirty = true;
+        self.render()
     }
 
-    // Draw specific updates for a window. If the update is behind another window, it will only be buffered.
+    // Collects the updates of every window, then draws only the cells that changed on screen.
     pub fn draw(&mut self) -> Result<()> {
-        let mut refresh = false;
-        let mut point_map = HashSet::new();
-        // ensure that windows below other windows do not draw over the top.
-        // also draw border and title if set.
-        let mut stdout = stdout();
         for window in self.windows.iter_mut() {
             let some_buffer = self.buffer.get_mut(&window.id);
             let buffer = match some_buffer {
                 Some(b) => b,
-                None => Err(ErrorKind::new(io::ErrorKind::Other, "Should always be Some here!"))?
+                None => Err(ErrorKind::other("Should always be Some here!"))?
             };
 
             let window_updates = if window.refresh {
                 buffer.clear();
-                refresh = true;
+                self.dirty = true;
                 window.get_state()?
             } else {
                 window.get_updates()?
@@ -123,37 +247,98 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Screen<T> {
                 if update_element.point.y > window_size.height || update_element.point.x > window_size.width {
                     continue;
                 }
-                let mut value = update_element
-                    .value
-                    .to_string()
-                    .on(Color::Rgb { r: 0, g: 0, b: 0 });
-                if let Some(fg) = update_element.fg {
-                    value = value.with(fg);
-                }
-                let absolute_x = window.location.x + update_element.point.x;
-                let absolute_y = window.location.y + update_element.point.y;
-
-                let point = (absolute_x, absolute_y).into();
-                buffer.insert(point, value.clone());
-                Screen::<T>::draw_value(&mut stdout,&mut point_map, point, value)?;
+                add_to_buffer(buffer, update_element, window_size.width, self.theme.window);
+                self.dirty = true;
             }
+        }
+        self.render()
+    }
 
-            for (key, _) in buffer {
-                point_map.insert(*key);
+    fn render(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        self.composite()?;
+        for y in 0..self.back.get_height() {
+            let changed = self.get_changed_cells(y);
+            let mut x = 0;
+            while x < self.back.get_width() {
+                if !changed[x as usize] {
+                    x += 1;
+                    continue;
+                }
+                // Print a run of changed cells that share a style in one go.
+                let start_x = x;
+                let first_cell = self.back.get(x, y).clone();
+                let mut content = String::new();
+                while x < self.back.get_width() && changed[x as usize] && self.back.get(x, y).has_same_style(&first_cell) {
+                    let cell = self.back.get(x, y);
+                    let is_wide = cell.is_wide();
+                    if !self.back.is_continuation(x, y) {
+                        content.push_str(if cell.value.is_empty() { " " } else { &cell.value });
+                    }
+                    x += 1;
+                    // A wide grapheme covers the next cell, so anything other than its
+                    // placeholder has to be printed from its own position.
+                    if is_wide && !self.back.is_continuation(x, y) {
+                        break;
+                    }
+                }
+                if !content.is_empty() {
+                    let style = ContentStyle {
+                        foreground_color: first_cell.style.fg,
+                        background_color: first_cell.style.bg,
+                        attributes: first_cell.style.attributes,
+                        ..ContentStyle::default()
+                    };
+                    self.backend.move_to(start_x, y)?;
+                    self.backend.print(StyledContent::new(style, content))?;
+                }
             }
         }
+        self.backend.hide_cursor()?;
+        self.backend.flush()?;
+        self.front.clone_from(&self.back);
+        self.dirty = false;
+        Ok(())
+    }
 
-        queue!(stdout,cursor::Hide)?;
-        stdout.flush()?;
-
-        if refresh {
-            self.refresh()?;
+    // Draws every window into the back grid, from the bottom window to the top one.
+    fn composite(&mut self) -> Result<()> {
+        self.back.clear();
+        for window in self.windows.iter().rev() {
+            let buffer = match self.buffer.get(&window.id) {
+                Some(b) => b,
+                None => Err(ErrorKind::other("Should always be Some here!"))?
+            };
+            for (point, cell) in buffer.iter() {
+                self.back.set(window.location + *point, cell.clone());
+            }
         }
         Ok(())
     }
 
-    pub fn add(&mut self, window:Window<T>) -> Result<()> {
+    fn get_changed_cells(&self, y: i32) -> Vec<bool> {
+        let width = self.back.get_width();
+        let mut changed: Vec<bool> = (0..width).map(|x| self.back.get(x, y) != self.front.get(x, y)).collect();
+        // A changed placeholder can only be drawn by printing its wide grapheme again, and a
+        // wide grapheme that is printed again overwrites the cell after it.
+        for x in (1..width).rev() {
+            if changed[x as usize] && self.back.is_continuation(x, y) {
+                changed[(x - 1) as usize] = true;
+            }
+        }
+        for x in 0..width - 1 {
+            if changed[x as usize] && self.back.get(x, y).is_wide() {
+                changed[(x + 1) as usize] = true;
+            }
+        }
+        changed
+    }
+
+    pub fn add(&mut self, mut window:Window<T>) -> Result<()> {
         let window_id = window.id;
+        window.set_theme(&self.theme);
         let some_idx = self.windows.binary_search_by_key(&window.z, |w| w.z);
         match some_idx {
             Ok(i) => {
@@ -166,6 +351,8 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Screen<T> {
         }
 
         self.buffer.insert(window_id, HashMap::new());
+        self.focused = None;
+        self.update_focus();
         self.refresh()?;
         Ok(())
     }
@@ -191,24 +378,170 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Screen<T> {
             }
         }
         if windows_removed {
+            self.update_focus();
             self.refresh()?;
         }
         Ok(())
     }
+}
 
-    fn draw_value(stdout: &mut Stdout, point_map: &mut HashSet<Point>, point: Point, value: StyledContent<String>) -> Result<()>{
-        let value_len = value.content().len();
-        if value_len == 0 {
-            return Ok(());
-        }
-        for (i,c) in value.content().chars().enumerate() {
-            let current_point = Point {x: point.x + i as i32, y: point.y};
-            if !point_map.contains(&current_point) {
-                point_map.insert(current_point);
-                let styled_char = StyledContent::new(value.style().clone(), c.to_string());
-                queue!(stdout, cursor::MoveTo(current_point.x as u16, current_point.y as u16), style::Print(styled_char))?;
-            }
-        }
-        Ok(())
+// A wide grapheme covers the cell after it, which gets an empty placeholder. One that would stick
+// out of the window is left out, and anything drawn over half of a wide grapheme removes it.
+fn add_to_buffer(buffer: &mut HashMap<Point, ScreenCell>, update: &UpdateElement, width: i32, window_style: Style) {
+    let mut cell = ScreenCell::from_update(update, window_style);
+    if cell.is_wide() && update.point.x + 1 > width {
+        cell.value = " ".to_string();
+    }
+    let left = update.point + (-1, 0).into();
+    if !cell.value.is_empty() && buffer.get(&left).is_some_and(|c| c.is_wide()) {
+        buffer.entry(left).and_modify(|c| c.value = " ".to_string());
+    }
+    if cell.is_wide() {
+        buffer.insert(update.point + (1, 0).into(), cell.placeholder());
+    }
+    buffer.insert(update.point, cell);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::test_backend::TestBackend;
+    use crate::screen::window::border_style::BorderStyle;
+    use crate::screen::window::button::ButtonComponent;
+    use crate::screen::window::checkbox::CheckboxComponent;
+    use crate::test_support::TestAction;
+
+    fn screen() -> Screen<TestAction, TestBackend> {
+        Screen::new_with_backend(TestBackend::new(20, 8)).unwrap()
+    }
+
+    // A 10x2 window around a 6x1 button, with the label starting 4 cells in and 1 down.
+    fn button_window(location: Point, z: i32, label: &str, action: TestAction, can_move: bool) -> Window<TestAction> {
+        let button = ButtonComponent::new(label.into(), (6, 1).into(), action);
+        Window::new(location, z, Box::new(button), BorderStyle::Single, "".into(), can_move, true)
+    }
+
+    // An 11x2 window around a 7x1 button, which leaves 3 columns for the title.
+    fn titled_window(title: &str) -> Window<TestAction> {
+        let button = ButtonComponent::new("Go".into(), (7, 1).into(), TestAction::Clicked(1));
+        Window::new((0, 0).into(), 0, Box::new(button), BorderStyle::Single, title.into(), false, true)
+    }
+
+    fn get_char(screen: &Screen<TestAction, TestBackend>, x: usize, y: i32) -> char {
+        screen.backend().get_line(y).chars().nth(x).unwrap()
+    }
+
+    fn get_text(screen: &Screen<TestAction, TestBackend>, x: usize, y: i32, width: usize) -> String {
+        screen.backend().get_line(y).chars().skip(x).take(width).collect()
+    }
+
+    #[test]
+    fn window_is_drawn_at_its_location() {
+        let mut screen = screen();
+        screen.add(button_window((1, 1).into(), 0, "Go", TestAction::Clicked(1), false)).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_char(&screen, 2, 1), '┏');
+        assert_eq!(get_text(&screen, 0, 2, 9), "  ┃  Go  ");
+        assert_eq!(get_char(&screen, 2, 3), '┗');
+        assert_eq!(screen.backend().get_line(0).trim(), "");
+    }
+
+    #[test]
+    fn click_reaches_the_component_under_it() {
+        let mut screen = screen();
+        screen.add(button_window((1, 1).into(), 0, "Go", TestAction::Clicked(1), false)).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(screen.handle_click(MouseAction::Left((5, 2).into())).unwrap(), vec![TestAction::Clicked(1)]);
+        assert_eq!(screen.handle_click(MouseAction::Left((15, 6).into())).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn click_on_the_close_button_closes_the_window() {
+        let mut screen = screen();
+        let window = button_window((1, 1).into(), 0, "Go", TestAction::Clicked(1), false);
+        let window_id = window.id;
+        screen.add(window).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_char(&screen, 7, 1), 'Ⓧ');
+        assert_eq!(screen.handle_click(MouseAction::Left((7, 1).into())).unwrap(), vec![TestAction::Close(window_id)]);
+
+        screen.remove_all(vec![window_id]).unwrap();
+        assert!(screen.backend().get_lines().iter().all(|line| line.trim().is_empty()));
+    }
+
+    #[test]
+    fn top_window_gets_the_click_where_windows_overlap() {
+        let mut screen = screen();
+        screen.add(button_window((0, 0).into(), 1, "AAAAAA", TestAction::Clicked(1), false)).unwrap();
+        screen.add(button_window((0, 0).into(), 0, "BBBBBB", TestAction::Clicked(2), false)).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_char(&screen, 4, 1), 'B');
+        assert_eq!(screen.handle_click(MouseAction::Left((4, 1).into())).unwrap(), vec![TestAction::Clicked(2)]);
+    }
+
+    #[test]
+    fn clicking_a_window_below_brings_it_to_the_front() {
+        let mut screen = screen();
+        screen.add(button_window((0, 0).into(), 0, "AAAAAA", TestAction::Clicked(1), true)).unwrap();
+        screen.add(button_window((3, 1).into(), 1, "BBBBBB", TestAction::Clicked(2), true)).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_char(&screen, 5, 2), '━');
+
+        // The first click only raises the window, the next one reaches the button.
+        assert_eq!(screen.handle_click(MouseAction::Left((11, 2).into())).unwrap(), vec![]);
+        screen.draw().unwrap();
+        assert_eq!(get_text(&screen, 5, 2, 6), "BBBBBB");
+        assert_eq!(screen.handle_click(MouseAction::Left((7, 2).into())).unwrap(), vec![TestAction::Clicked(2)]);
+    }
+
+    #[test]
+    fn dragging_the_border_moves_the_window() {
+        let mut screen = screen();
+        screen.add(button_window((0, 0).into(), 0, "Go", TestAction::Clicked(1), true)).unwrap();
+        screen.draw().unwrap();
+        screen.handle_click(MouseAction::Drag((4, 0).into(), (7, 2).into())).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(screen.backend().get_line(1).trim(), "");
+        assert_eq!(get_text(&screen, 4, 3, 8), "┃  Go  ┃");
+    }
+
+    #[test]
+    fn clicked_checkbox_is_drawn_ticked() {
+        let mut screen = screen();
+        let checkbox = CheckboxComponent::new("Sound".into(), false, Some(TestAction::Refresh));
+        screen.add(Window::new((0, 0).into(), 0, Box::new(checkbox), BorderStyle::None, "".into(), false, false)).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_text(&screen, 0, 0, 9), "[ ] Sound");
+
+        assert_eq!(screen.handle_click(MouseAction::Left((1, 0).into())).unwrap(), vec![TestAction::Refresh]);
+        screen.draw().unwrap();
+        assert_eq!(get_text(&screen, 0, 0, 9), "[x] Sound");
+    }
+
+    #[test]
+    fn title_graphemes_are_drawn_whole() {
+        let mut screen = screen();
+        screen.add(titled_window("e\u{301}x")).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(screen.backend().get_cell(3, 0).value, "e\u{301}");
+        assert!(screen.backend().get_line(0).starts_with(" ┏┫e\u{301}x┣━Ⓧ ┓"));
+    }
+
+    #[test]
+    fn wide_title_is_cut_between_graphemes() {
+        let mut screen = screen();
+        screen.add(titled_window("🇳🇱🇳🇱")).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(screen.backend().get_cell(3, 0).value, "🇳🇱");
+        assert_eq!(screen.backend().get_cell(4, 0).value, "");
+        assert!(screen.backend().get_line(0).starts_with(" ┏┫🇳🇱┣━Ⓧ ┓"));
+    }
+
+    #[test]
+    fn wide_grapheme_that_does_not_fit_the_title_is_left_out() {
+        let mut screen = screen();
+        screen.add(titled_window("ab👨\u{200D}👩\u{200D}👧")).unwrap();
+        screen.draw().unwrap();
+        assert!(screen.backend().get_line(0).starts_with(" ┏┫ab┣━Ⓧ ┓"));
     }
 }
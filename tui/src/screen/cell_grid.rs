@@ -0,0 +1,67 @@
+use crate::screen::point::Point;
+use crate::screen::screen_cell::ScreenCell;
+
+// The whole terminal as rows of cells. Writes outside the grid are dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellGrid {
+    width: i32,
+    height: i32,
+    cells: Vec<ScreenCell>,
+    // Returned for cells outside the grid.
+    blank: ScreenCell,
+}
+
+impl CellGrid {
+    pub fn new(width: i32, height: i32) -> Self {
+        let width = width.max(0);
+        let height = height.max(0);
+        CellGrid {
+            width,
+            height,
+            cells: vec![ScreenCell::blank(); (width * height) as usize],
+            blank: ScreenCell::blank(),
+        }
+    }
+
+    pub fn get_width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> &ScreenCell {
+        match self.get_index(x, y) {
+            Some(idx) => &self.cells[idx],
+            None => &self.blank,
+        }
+    }
+
+    // A cell set over the second half of a wide grapheme removes the grapheme, as it would on
+    // a terminal.
+    pub fn set(&mut self, point: Point, cell: ScreenCell) {
+        if let Some(idx) = self.get_index(point.x, point.y) {
+            if !cell.value.is_empty() && self.is_continuation(point.x, point.y) {
+                self.cells[idx - 1].value = " ".to_string();
+            }
+            self.cells[idx] = cell;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(ScreenCell::blank());
+    }
+
+    // Wide graphemes are followed by an empty placeholder cell that the grapheme itself covers.
+    pub fn is_continuation(&self, x: i32, y: i32) -> bool {
+        x > 0 && self.get(x, y).value.is_empty() && self.get(x - 1, y).is_wide()
+    }
+
+    fn get_index(&self, x: i32, y: i32) -> Option<usize> {
+        match x >= 0 && y >= 0 && x < self.width && y < self.height {
+            true => Some((y * self.width + x) as usize),
+            false => None,
+        }
+    }
+}
@@ -7,7 +7,7 @@ pub struct Dimension {
 impl From<Dimension> for (i32, i32) {
     fn from(c: Dimension) -> (i32, i32) {
         let Dimension {width, height} = c;
-        return (width, height);
+        (width, height)
     }
 }
 
@@ -9,7 +9,7 @@ pub struct Point {
 impl From<Point> for (i32, i32) {
     fn from(c: Point) -> (i32, i32) {
         let Point {x, y} = c;
-        return (x, y);
+        (x, y)
     }
 }
 
@@ -0,0 +1,38 @@
+use crate::screen::style::Style;
+use crate::screen::text;
+use crate::screen::window::update_element::UpdateElement;
+
+// One terminal cell as it is, or will be, on screen. A colour of None is the terminal default.
+// The value is a whole grapheme, or empty for the second cell of a wide one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenCell {
+    pub value: String,
+    pub style: Style,
+}
+
+impl ScreenCell {
+    pub fn blank() -> Self {
+        ScreenCell { value: " ".to_string(), style: Style::default() }
+    }
+
+    // The window style of the theme shows through where an update has no colour of its own.
+    pub fn from_update(update: &UpdateElement, window_style: Style) -> Self {
+        ScreenCell {
+            value: update.value.clone(),
+            style: update.style.over(window_style),
+        }
+    }
+
+    // The empty cell after a wide grapheme, which the grapheme covers.
+    pub fn placeholder(&self) -> Self {
+        ScreenCell { value: String::new(), style: self.style }
+    }
+
+    pub fn is_wide(&self) -> bool {
+        text::get_width(&self.value) == 2
+    }
+
+    pub fn has_same_style(&self, other: &ScreenCell) -> bool {
+        self.style == other.style
+    }
+}
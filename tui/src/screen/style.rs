@@ -0,0 +1,37 @@
+use crossterm::style::{Attribute, Attributes, Color};
+
+// How a cell is drawn. A colour of None is taken from the theme of the screen.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub attributes: Attributes,
+}
+
+impl Style {
+    pub fn new(fg: Option<Color>, bg: Option<Color>) -> Self {
+        Style { fg, bg, attributes: Attributes::default() }
+    }
+
+    pub fn new_with_attributes(fg: Option<Color>, bg: Option<Color>, attributes: &[Attribute]) -> Self {
+        Style { fg, bg, attributes: Attributes::from(attributes) }
+    }
+
+    pub fn from_fg(fg: Color) -> Self {
+        Style::new(Some(fg), None)
+    }
+
+    pub fn from_bg(bg: Color) -> Self {
+        Style::new(None, Some(bg))
+    }
+
+    // This style drawn over other, so other shows through where this one has no colour, and
+    // the attributes of both are used.
+    pub fn over(&self, other: Style) -> Style {
+        Style {
+            fg: self.fg.or(other.fg),
+            bg: self.bg.or(other.bg),
+            attributes: other.attributes | self.attributes,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+use crate::screen::point::Point;
+use crate::screen::style::Style;
+use crate::screen::window::update_element::UpdateElement;
+
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+
+// Text is laid out by grapheme, so a character and its combining marks or an emoji sequence are
+// never split, and each grapheme takes as many columns as the terminal gives it.
+
+// Columns the text takes on screen.
+pub fn get_width(text: &str) -> i32 {
+    text.graphemes(true).map(get_grapheme_width).sum()
+}
+
+// The longest start of the text that fits in width columns.
+pub fn truncate(text: &str, width: i32) -> &str {
+    let mut used = 0;
+    for (idx, grapheme) in text.grapheme_indices(true) {
+        used += get_grapheme_width(grapheme);
+        if used > width {
+            return &text[..idx];
+        }
+    }
+    text
+}
+
+// Cuts the text to width columns, then pads it with spaces to exactly width columns.
+pub fn fit(text: &str, width: i32) -> String {
+    let text = truncate(text, width);
+    format!("{}{}", text, " ".repeat((width - get_width(text)).max(0) as usize))
+}
+
+// Breaks the text into lines of at most width columns, between words where it can. Words
+// longer than a line are split, and new lines in the text always start a line.
+pub fn wrap(text: &str, width: i32) -> Vec<String> {
+    let mut lines = vec![];
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word;
+            if !line.is_empty() && get_width(&line) + 1 + get_width(word) <= width {
+                line.push(' ');
+                line.push_str(word);
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            while get_width(word) > width && width > 0 {
+                // At least one grapheme goes on each line, even one wider than the line.
+                let mut head = truncate(word, width);
+                if head.is_empty() {
+                    head = word.graphemes(true).next().unwrap_or(word);
+                }
+                lines.push(head.to_string());
+                word = &word[head.len()..];
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+// Updates drawing the text from point, one per grapheme, cut off at max_width columns. Wide
+// graphemes take two columns and the screen covers the second one, so nothing is pushed for it.
+pub fn layout(text: &str, point: Point, max_width: i32, style: Style) -> Vec<UpdateElement> {
+    let mut updates = vec![];
+    let mut x = 0;
+    for grapheme in truncate(text, max_width).graphemes(true) {
+        let width = get_grapheme_width(grapheme);
+        if width == 0 {
+            // A control character, or marks without anything to combine with.
+            continue;
+        }
+        updates.push(UpdateElement { point: point + (x, 0).into(), value: grapheme.to_string(), style });
+        x += width;
+    }
+    updates
+}
+
+// Terminals draw the marks and joined emoji of a grapheme over its first char, so that decides
+// the width, unless the grapheme asks for emoji presentation or is a flag, which are wide.
+fn get_grapheme_width(grapheme: &str) -> i32 {
+    let mut chars = grapheme.chars();
+    let first = match chars.next() {
+        Some(first) => first,
+        None => return 0,
+    };
+    let width = first.width().unwrap_or(0) as i32;
+    let is_flag = is_regional_indicator(first) && chars.next().is_some_and(is_regional_indicator);
+    match width > 0 && (is_flag || grapheme.contains(EMOJI_PRESENTATION)) {
+        true => 2,
+        false => width,
+    }
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_values(updates: &[UpdateElement]) -> Vec<(i32, &str)> {
+        updates.iter().map(|u| (u.point.x, u.value.as_str())).collect()
+    }
+
+    #[test]
+    fn combining_marks_stay_with_their_letter() {
+        let updates = layout("e\u{301}x", (0, 0).into(), 10, Style::default());
+        assert_eq!(get_values(&updates), vec![(0, "e\u{301}"), (1, "x")]);
+    }
+
+    #[test]
+    fn emoji_sequences_take_one_wide_cell() {
+        let family = "👨\u{200D}👩\u{200D}👧";
+        assert_eq!(get_width(family), 2);
+        assert_eq!(get_width("🇳🇱"), 2);
+        assert_eq!(get_width("❤\u{FE0F}"), 2);
+        let updates = layout(&format!("{}🇳🇱!", family), (1, 0).into(), 10, Style::default());
+        assert_eq!(get_values(&updates), vec![(1, family), (3, "🇳🇱"), (5, "!")]);
+    }
+
+    #[test]
+    fn truncate_never_splits_a_grapheme() {
+        assert_eq!(truncate("ab🇳🇱", 3), "ab");
+        assert_eq!(truncate("ae\u{301}", 2), "ae\u{301}");
+        assert_eq!(layout("ab😀", (0, 0).into(), 3, Style::default()).len(), 2);
+    }
+
+    #[test]
+    fn lone_marks_and_control_characters_are_left_out() {
+        let updates = layout("\u{301}a\tb", (0, 0).into(), 10, Style::default());
+        assert_eq!(get_values(&updates), vec![(0, "a"), (1, "b")]);
+    }
+}
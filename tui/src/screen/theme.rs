@@ -0,0 +1,128 @@
+use crossterm::style::{Attribute, Color};
+use crate::screen::style::Style;
+
+// The styles every window and component is drawn with. Styles are drawn over window, so a
+// colour they leave as None is the one of window.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Theme {
+    // The name the theme is picked by in the config file.
+    pub name: &'static str,
+    // Text and background of windows. None keeps the colour of the terminal.
+    pub window: Style,
+    pub border: Style,
+    pub focused_border: Style,
+    // Drawn over the border style of the window.
+    pub title: Style,
+    pub focused_title: Style,
+    pub close_button: Style,
+    pub button: Style,
+    // Row picked with the keyboard in dialogs.
+    pub selected: Style,
+    pub input: Style,
+    pub input_cursor: Style,
+    pub error: Style,
+    pub win: Style,
+    pub lose: Style,
+    // Colours games tell counts apart with, such as the minesweeper numbers 1 to 8.
+    pub palette: [Style; 8],
+}
+
+impl Theme {
+    // The colours of the terminal, with bright colours that read well on a dark background.
+    pub fn dark() -> Self {
+        Theme {
+            name: "dark",
+            window: Style::default(),
+            border: Style::default(),
+            focused_border: Style::from_fg(Color::Yellow),
+            title: Style::default(),
+            focused_title: Style::new_with_attributes(None, None, &[Attribute::Bold]),
+            close_button: Style::default(),
+            button: Style::default(),
+            selected: Style::from_bg(Color::DarkGrey),
+            input: Style::from_bg(Color::DarkGrey),
+            input_cursor: Style::from_bg(Color::Grey),
+            error: Style::from_fg(Color::Red),
+            win: Style::from_fg(Color::Green),
+            lose: Style::from_fg(Color::Red),
+            palette: [
+                Style::from_fg(Color::White),
+                Style::from_fg(Color::Cyan),
+                Style::from_fg(Color::Green),
+                Style::from_fg(Color::Yellow),
+                Style::from_fg(Color::DarkYellow),
+                Style::from_fg(Color::DarkMagenta),
+                Style::from_fg(Color::Red),
+                Style::from_fg(Color::DarkRed),
+            ],
+        }
+    }
+
+    // Dark text on a white background, with the number colours of the original minesweeper.
+    pub fn light() -> Self {
+        Theme {
+            name: "light",
+            window: Style::new(Some(Color::Black), Some(Color::White)),
+            border: Style::from_fg(Color::DarkGrey),
+            focused_border: Style::from_fg(Color::DarkBlue),
+            title: Style::from_fg(Color::Black),
+            focused_title: Style::new_with_attributes(None, None, &[Attribute::Bold]),
+            close_button: Style::default(),
+            button: Style::default(),
+            selected: Style::from_bg(Color::Grey),
+            input: Style::from_bg(Color::Grey),
+            input_cursor: Style::new(Some(Color::White), Some(Color::DarkGrey)),
+            error: Style::from_fg(Color::DarkRed),
+            win: Style::from_fg(Color::DarkGreen),
+            lose: Style::from_fg(Color::DarkRed),
+            palette: [
+                Style::from_fg(Color::Blue),
+                Style::from_fg(Color::DarkGreen),
+                Style::from_fg(Color::Red),
+                Style::from_fg(Color::DarkBlue),
+                Style::from_fg(Color::DarkRed),
+                Style::from_fg(Color::DarkCyan),
+                Style::from_fg(Color::Black),
+                Style::from_fg(Color::DarkGrey),
+            ],
+        }
+    }
+
+    // White and bright colours on black, in bold, and the keyboard selection in reverse video.
+    pub fn high_contrast() -> Self {
+        let bold = |color| Style::new_with_attributes(Some(color), None, &[Attribute::Bold]);
+        let bold_underlined = |color| Style::new_with_attributes(Some(color), None, &[Attribute::Bold, Attribute::Underlined]);
+        Theme {
+            name: "high-contrast",
+            window: Style::new(Some(Color::White), Some(Color::Black)),
+            border: Style::from_fg(Color::White),
+            focused_border: bold(Color::Yellow),
+            title: bold(Color::White),
+            focused_title: bold(Color::Yellow),
+            close_button: Style::new_with_attributes(None, None, &[Attribute::Bold]),
+            button: Style::new_with_attributes(None, None, &[Attribute::Bold]),
+            selected: Style::new_with_attributes(None, None, &[Attribute::Reverse]),
+            input: Style::new_with_attributes(None, None, &[Attribute::Underlined]),
+            input_cursor: Style::new_with_attributes(None, None, &[Attribute::Reverse]),
+            error: bold(Color::Red),
+            win: bold(Color::Green),
+            lose: bold(Color::Red),
+            palette: [
+                bold(Color::White),
+                bold(Color::Cyan),
+                bold(Color::Green),
+                bold(Color::Yellow),
+                bold(Color::Magenta),
+                bold(Color::Red),
+                bold_underlined(Color::Cyan),
+                bold_underlined(Color::Yellow),
+            ],
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
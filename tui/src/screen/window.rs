@@ -1,24 +1,39 @@
 pub mod button;
 mod border_elements;
 pub mod border_style;
+pub mod checkbox;
 pub mod component;
 pub mod has_close_action;
+pub mod key_action;
+pub mod label;
+pub mod list;
 pub mod mouse_action;
+pub mod number_input;
+pub mod progress_bar;
+pub mod radio_group;
+pub mod text_block;
+pub mod text_input;
 pub mod update_element;
 
 use crossterm::Result;
 use std::cmp::Ordering;
 use std::fmt::Debug;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 use crate::screen::dimension::Dimension;
 use crate::screen::point::Point;
+use crate::screen::text;
+use crate::screen::theme::Theme;
 use crate::screen::window::border_elements::BorderElements;
 use crate::screen::window::border_style::BorderStyle;
 use crate::screen::window::component::Component;
 use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::key_action::KeyAction;
 use crate::screen::window::mouse_action::MouseAction;
 use crate::screen::window::update_element::UpdateElement;
 
+type UpdatesGetter<T> = fn(&mut Box<dyn Component<T>>) -> Result<Vec<UpdateElement>>;
+
 #[derive(Debug)]
 pub struct Window<T: HasCloseAndRefreshActions + PartialEq + Clone> {
     pub id: Uuid,
@@ -29,7 +44,9 @@ pub struct Window<T: HasCloseAndRefreshActions + PartialEq + Clone> {
     component: Box<dyn Component<T>>,
     pub refresh: bool,
     pub can_move: bool,
-    close_point: Option<Point>,
+    can_close: bool,
+    focused: bool,
+    theme: Theme,
 }
 
 impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Window<T> {
@@ -43,15 +60,7 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Window<T> {
         can_close: bool
     ) -> Self {
         let id = component.get_id();
-        let width =
-            Window::<T>::get_window_size(component.get_size(), border_style).width;
-
-        let close_point = match can_close {
-            true => Some((width - 4, 0).into()),
-            false => None,
-        };
-
-        return Window {
+        Window {
             id,
             location,
             z,
@@ -60,8 +69,30 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Window<T> {
             component,
             can_move,
             refresh: true,
-            close_point
-        };
+            can_close,
+            focused: false,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    // The border of the focused window is drawn in a different colour.
+    pub fn set_focused(&mut self, focused: bool) {
+        if self.focused != focused {
+            self.focused = focused;
+            self.refresh = true;
+        }
+    }
+
+    // Worked out from the current size, as components can change size.
+    fn get_close_point(&self) -> Option<Point> {
+        match self.can_close {
+            true => Some((self.get_size().width - 4, 0).into()),
+            false => None,
+        }
     }
 
     fn get_window_size(component_size: Dimension, border_style: BorderStyle) -> Dimension{
@@ -70,19 +101,18 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Window<T> {
             width += 4;
             height += 1;
         }
-        return (width, height).into();
+        (width, height).into()
     }
 
     fn draw_border(&self) -> Result<Vec<UpdateElement>> {
         let border_elements = BorderElements::new(self.border_style);
         let mut updates = vec![];
-        let mut title = self.border_title.clone();
-        let mut title_len = title.chars().count() as i32;
+        let (border, title_style) = match self.focused {
+            true => (self.theme.focused_border, self.theme.focused_title.over(self.theme.focused_border)),
+            false => (self.theme.border, self.theme.title.over(self.theme.border)),
+        };
+        let close_style = self.theme.close_button.over(border);
         let size = self.get_size();
-        if title_len >= size.width {
-            title = Box::from(&title[..(size.width - 2) as usize]);
-            title_len = size.width - 1;
-        }
         let top_left:Point = (0, 0).into();
         let b_top_left = top_left + (1,0).into();
         let top_right:Point = (size.width, 0).into();
@@ -92,82 +122,84 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Window<T> {
         let bottom_right:Point = (size.width, size.height).into();
         let b_bottom_right = bottom_right + (-2,0).into();
         for y in top_left.y..bottom_left.y+1 {
-            updates.push(UpdateElement {point: (top_left.x, y).into(), value: ' ', fg: None});
+            updates.push(UpdateElement {point: (top_left.x, y).into(), value: ' '.to_string(), style: border});
         }
         for y in top_right.y..bottom_right.y+1 {
-            updates.push(UpdateElement {point: (top_right.x-1, y).into(), value: ' ', fg: None});
+            updates.push(UpdateElement {point: (top_right.x-1, y).into(), value: ' '.to_string(), style: border});
         }
 
         if b_top_left.y >= 0 {
             if b_top_left.x >= 0 {
                 // draw top_left corner.
-                updates.push(UpdateElement {point: b_top_left, value: border_elements.top_left, fg: None});
+                updates.push(UpdateElement {point: b_top_left, value: border_elements.top_left.to_string(), style: border});
             }
             // draw top_right corner.
-            updates.push(UpdateElement {point: b_top_right, value: border_elements.top_right, fg: None});
+            updates.push(UpdateElement {point: b_top_right, value: border_elements.top_right.to_string(), style: border});
 
             let mut top_line_right_offset = 0;
-            if let Some(close_pos) = self.close_point {
+            if let Some(close_pos) = self.get_close_point() {
                 top_line_right_offset = 2;
                 // draw Close button.
-                updates.push(UpdateElement {point: close_pos, value: 'Ⓧ', fg: None});
-                updates.push(UpdateElement {point: close_pos + (1,0).into(), value: ' ', fg: None});
+                updates.push(UpdateElement {point: close_pos, value: 'Ⓧ'.to_string(), style: close_style});
+                updates.push(UpdateElement {point: close_pos + (1,0).into(), value: ' '.to_string(), style: border});
             }
 
+            // The title and its frame chars have to fit between the corners and the close button.
+            let title_width = b_top_right.x - top_line_right_offset - b_top_left.x - 3;
+            let title = text::truncate(&self.border_title, title_width);
+            let title_len = text::get_width(title);
             let mut top_line_offset = 1;
             if title_len > 0 {
                 top_line_offset = title_len + 3;
                 // draw pre-title char
-                updates.push(UpdateElement {point: (b_top_left.x + 1, b_top_left.y).into(), value: border_elements.label_frame_left, fg: None});
+                updates.push(UpdateElement {point: (b_top_left.x + 1, b_top_left.y).into(), value: border_elements.label_frame_left.to_string(), style: border});
                 // draw title
-                for x in b_top_left.x + 2..b_top_left.x + 2 + title_len {
-                    updates.push(UpdateElement {point: (x, b_top_left.y).into(), value: title.chars().nth(x as usize - 3).unwrap(), fg: None});
-                }
+                updates.append(&mut text::layout(title, (b_top_left.x + 2, b_top_left.y).into(), title_len, title_style));
                 // draw post-title char
-                updates.push(UpdateElement {point: (b_top_left.x + 2 + title_len, b_top_left.y).into(), value: border_elements.label_frame_right, fg: None});
+                updates.push(UpdateElement {point: (b_top_left.x + 2 + title_len, b_top_left.y).into(), value: border_elements.label_frame_right.to_string(), style: border});
             }
             // draw from top_left to top_right.
             for x in b_top_left.x + top_line_offset..b_top_right.x - top_line_right_offset {
-                updates.push(UpdateElement {point: (x, b_top_left.y).into(), value: border_elements.horizontal, fg: None});
+                updates.push(UpdateElement {point: (x, b_top_left.y).into(), value: border_elements.horizontal.to_string(), style: border});
             }
         }
         if top_left.x >= 0 {
             // draw bottom_left corner.
-            updates.push(UpdateElement {point: b_bottom_left.into(), value: border_elements.bottom_left, fg: None});
+            updates.push(UpdateElement {point: b_bottom_left, value: border_elements.bottom_left.to_string(), style: border});
             // draw from top_left to bottom_left.
             for y in (b_top_left.y + 1)..b_bottom_left.y {
-                updates.push(UpdateElement {point: (b_top_left.x, y).into(), value: border_elements.vertical, fg: None});
+                updates.push(UpdateElement {point: (b_top_left.x, y).into(), value: border_elements.vertical.to_string(), style: border});
             }
         }
         // draw bottom_right corner.
-        updates.push(UpdateElement {point: b_bottom_right.into(), value: border_elements.bottom_right, fg: None});
+        updates.push(UpdateElement {point: b_bottom_right, value: border_elements.bottom_right.to_string(), style: border});
         // draw from bottom_left to bottom_right
         for x in (b_bottom_left.x + 1)..b_bottom_right.x {
-            updates.push(UpdateElement {point: (x, b_bottom_left.y).into(), value: border_elements.horizontal, fg: None});
+            updates.push(UpdateElement {point: (x, b_bottom_left.y).into(), value: border_elements.horizontal.to_string(), style: border});
         }
         // draw from top_right to bottom_right
         for y in (b_top_right.y + 1)..b_bottom_right.y {
-            updates.push(UpdateElement {point: (b_top_right.x, y).into(), value: border_elements.vertical, fg: None});
+            updates.push(UpdateElement {point: (b_top_right.x, y).into(), value: border_elements.vertical.to_string(), style: border});
         }
         Ok(updates)
     }
-    fn get_updates_or_state(&mut self, updates_getter: fn (&mut Box<dyn Component<T>>) -> Result<Vec<UpdateElement>>) -> Result<Vec<UpdateElement>> {
+    fn get_updates_or_state(&mut self, updates_getter: UpdatesGetter<T>) -> Result<Vec<UpdateElement>> {
         let mut updates = match self.border_style != BorderStyle::None {
             true => self.draw_border()?,
             false => vec![],
         };
 
-        for update in updates_getter(&mut self.component)?.iter() {
+        for update in updates_getter(&mut self.component)? {
             let point = match self.border_style != BorderStyle::None {
                 true => Point{x: update.point.x + 2, y: update.point.y + 1 },
                 false => update.point,
             };
-            updates.push(UpdateElement {point, value: update.value, fg: update.fg });
+            updates.push(UpdateElement {point, ..update});
         }
 
         self.refresh = false;
 
-        return Ok(updates);
+        Ok(updates)
     }
 }
 
@@ -177,7 +209,7 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for Window<T
     }
 
     fn get_size(&self) -> Dimension {
-        Window::<T>::get_window_size(self.component.get_size().clone(), self.border_style)
+        Window::<T>::get_window_size(self.component.get_size(), self.border_style)
     }
 
     fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
@@ -196,7 +228,7 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for Window<T
                 || action_point.y == 0 || action_point.y == size.height){
             match mouse_action {
                 MouseAction::Left(_) => {
-                    if let Some(close_point) = self.close_point {
+                    if let Some(close_point) = self.get_close_point() {
                         if action_point == close_point || action_point == close_point + (1,0).into() {
                             return Ok(vec![T::get_close_action(self.get_id())])
                         }
@@ -242,6 +274,34 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for Window<T
         }
         Ok(vec![])
     }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<T>>> {
+        let actions = self.component.handle_key(key)?;
+        if let Some(actions) = &actions {
+            if actions.contains(&T::get_refresh_action()) {
+                self.refresh = true;
+            }
+        }
+        Ok(actions)
+    }
+
+    fn get_time_until_tick(&self, now: Instant) -> Option<Duration> {
+        self.component.get_time_until_tick(now)
+    }
+
+    fn on_tick(&mut self, now: Instant) -> Result<Vec<T>> {
+        let actions = self.component.on_tick(now)?;
+        if actions.contains(&T::get_refresh_action()) {
+            self.refresh = true;
+        }
+        Ok(actions)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+        self.component.set_theme(theme);
+        self.refresh = true;
+    }
 }
 
 fn calculate_relative_x_y<T: HasCloseAndRefreshActions + PartialEq + Clone>(window: &Window<T>, point: Point) -> Point{
@@ -255,7 +315,7 @@ fn calculate_relative_x_y<T: HasCloseAndRefreshActions + PartialEq + Clone>(wind
 
 impl<T: HasCloseAndRefreshActions + PartialEq + Clone> PartialEq for Window<T> {
     fn eq(&self, other: &Self) -> bool {
-        return other.id == self.id;
+        other.id == self.id
     }
 }
 
@@ -268,6 +328,6 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> PartialOrd for Window<T>
         } else if self.z == other.z {
             return Some(Ordering::Equal);
         }
-        return None;
+        None
     }
 }
@@ -1,8 +1,12 @@
 use crossterm::Result;
 use uuid::Uuid;
 use crate::screen::dimension::Dimension;
+use crate::screen::style::Style;
+use crate::screen::text;
+use crate::screen::theme::Theme;
 use crate::screen::window::component::Component;
 use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::key_action::KeyAction;
 use crate::screen::window::mouse_action::MouseAction;
 use crate::screen::window::update_element::UpdateElement;
 
@@ -11,19 +15,21 @@ pub struct ButtonComponent<T: HasCloseAndRefreshActions + PartialEq + Clone> {
     id: Uuid,
     label: Box<str>,
     size: Dimension,
+    style: Style,
     changed: bool,
     pub click_action: T,
 }
 
 impl<T: HasCloseAndRefreshActions + PartialEq + Clone> ButtonComponent<T> {
     pub fn new(label: Box<str>, size: Dimension, click_action: T) -> Self {
-        return ButtonComponent {
+        ButtonComponent {
             id: Uuid::new_v4(),
             label,
             size,
+            style: Style::default(),
             changed: true,
             click_action,
-        };
+        }
     }
 }
 
@@ -33,34 +39,22 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for ButtonCo
     }
 
     fn get_size(&self) -> Dimension {
-        return self.size;
+        self.size
     }
 
     fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
         let mut updates = vec![];
         if self.changed {
-            let mut y = 0;
-            if y > 1 {
-                y = y / 2;
-            }
+            // The label goes on the middle row, or the one above the middle for an even height.
+            let y = (self.size.height - 1).max(0) / 2;
             let mut x = 0;
-            let label_len = self.label.len() as i32;
+            let label_len = text::get_width(&self.label);
             if self.size.width > label_len {
-                x = self.size.width / 2 as i32 - label_len / 2 as i32;
-            }
-            for c in self.label.chars() {
-                updates.push(UpdateElement {
-                    point: (x,y).into(),
-                    value: c,
-                    fg: None,
-                });
-                x += 1;
-                if x >= self.size.width {
-                    break;
-                }
+                x = self.size.width / 2_i32 - label_len / 2_i32;
             }
+            updates = text::layout(&self.label, (x, y).into(), self.size.width - x, self.style);
         }
-        return Ok(updates);
+        Ok(updates)
     }
 
     fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
@@ -73,4 +67,16 @@ impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for ButtonCo
             _ => vec![]
         })
     }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<T>>> {
+        Ok(match key {
+            KeyAction::Enter | KeyAction::Char(' ') => Some(vec![self.click_action.clone()]),
+            _ => None
+        })
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.style = theme.button;
+        self.changed = true;
+    }
 }
@@ -0,0 +1,122 @@
+use crossterm::Result;
+use uuid::Uuid;
+use crate::screen::dimension::Dimension;
+use crate::screen::style::Style;
+use crate::screen::text;
+use crate::screen::window::component::Component;
+use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::key_action::KeyAction;
+use crate::screen::window::mouse_action::MouseAction;
+use crate::screen::window::update_element::UpdateElement;
+
+// "[x] label", ticked and unticked by left clicking it or with Space or Enter.
+#[derive(Debug, Clone)]
+pub struct CheckboxComponent<T: HasCloseAndRefreshActions + PartialEq + Clone> {
+    id: Uuid,
+    label: Box<str>,
+    checked: bool,
+    changed: bool,
+    change_action: Option<T>,
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> CheckboxComponent<T> {
+    pub fn new(label: Box<str>, checked: bool, change_action: Option<T>) -> Self {
+        CheckboxComponent {
+            id: Uuid::new_v4(),
+            label,
+            checked,
+            changed: true,
+            change_action,
+        }
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+        self.changed = true;
+    }
+
+    fn toggle(&mut self) -> Vec<T> {
+        self.set_checked(!self.checked);
+        self.change_action.clone().into_iter().collect()
+    }
+
+    fn get_text(&self) -> String {
+        format!("[{}] {}", if self.checked { 'x' } else { ' ' }, self.label)
+    }
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for CheckboxComponent<T> {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        (text::get_width(&self.get_text()), 1).into()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        self.changed = false;
+        let text = self.get_text();
+        Ok(text::layout(&text, (0, 0).into(), text::get_width(&text), Style::default()))
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        match self.changed {
+            true => self.get_state(),
+            false => Ok(vec![]),
+        }
+    }
+
+    fn handle_click(&mut self, click: MouseAction) -> Result<Vec<T>> {
+        Ok(match click {
+            MouseAction::Left(_) => self.toggle(),
+            _ => vec![],
+        })
+    }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<T>>> {
+        Ok(match key {
+            KeyAction::Enter | KeyAction::Char(' ') => Some(self.toggle()),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{draw_component, get_line, TestAction};
+
+    #[test]
+    fn space_and_enter_toggle_it() {
+        let mut checkbox = CheckboxComponent::new("Sound".into(), false, Some(TestAction::Refresh));
+        assert_eq!(checkbox.handle_key(KeyAction::Char(' ')).unwrap(), Some(vec![TestAction::Refresh]));
+        assert!(checkbox.is_checked());
+        checkbox.handle_key(KeyAction::Enter).unwrap();
+        assert!(!checkbox.is_checked());
+        assert_eq!(checkbox.handle_key(KeyAction::Char('x')).unwrap(), None);
+        assert!(!checkbox.is_checked());
+    }
+
+    #[test]
+    fn only_left_clicks_toggle_it() {
+        let mut checkbox = CheckboxComponent::<TestAction>::new("Sound".into(), true, None);
+        assert_eq!(checkbox.handle_click(MouseAction::Right((0, 0).into())).unwrap(), vec![]);
+        assert!(checkbox.is_checked());
+        checkbox.handle_click(MouseAction::Left((0, 0).into())).unwrap();
+        assert!(!checkbox.is_checked());
+    }
+
+    #[test]
+    fn tick_is_drawn() {
+        let mut screen = draw_component(CheckboxComponent::new("Sound".into(), false, None));
+        assert_eq!(get_line(&screen, 0), "[ ] Sound");
+        screen.handle_key(KeyAction::Char(' ')).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_line(&screen, 0), "[x] Sound");
+    }
+}
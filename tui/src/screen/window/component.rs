@@ -1,7 +1,10 @@
 use std::fmt::{Debug, Formatter};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 use crate::screen::dimension::Dimension;
+use crate::screen::theme::Theme;
 use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::key_action::KeyAction;
 use crate::screen::window::mouse_action::MouseAction;
 use crate::screen::window::update_element::UpdateElement;
 
@@ -11,10 +14,24 @@ pub trait Component<T: HasCloseAndRefreshActions + PartialEq + Clone> {
     fn get_state(&mut self) -> crossterm::Result<Vec<UpdateElement>>;
     fn get_updates(&mut self) -> crossterm::Result<Vec<UpdateElement>>;
     fn handle_click(&mut self, click: MouseAction) -> crossterm::Result<Vec<T>>;
+    // Returns None when the key is not used, so it can be handled further up.
+    fn handle_key(&mut self, _key: KeyAction) -> crossterm::Result<Option<Vec<T>>> {
+        Ok(None)
+    }
+    // How long until the component wants on_tick to be called, or None when it has nothing to do.
+    fn get_time_until_tick(&self, _now: Instant) -> Option<Duration> {
+        None
+    }
+    fn on_tick(&mut self, _now: Instant) -> crossterm::Result<Vec<T>> {
+        Ok(vec![])
+    }
+    // Called when the component is added to a screen and when the theme changes, before the
+    // component is drawn again.
+    fn set_theme(&mut self, _theme: &Theme) {}
 }
 
-impl<T> Debug for dyn Component<T> {
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Debug for dyn Component<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        write!(f, "Component {}", self.get_id())
     }
 }
@@ -0,0 +1,69 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use serde::Deserialize;
+
+// Keys are read from config files by name, e.g. "up", "enter", "space" or a single character.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum KeyAction {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Escape,
+    Backspace,
+    Delete,
+    Tab,
+    BackTab,
+    Home,
+    End,
+}
+
+impl Display for KeyAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyAction::Char(c) => write!(f, "Char '{}'", c),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+impl FromStr for KeyAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(KeyAction::Char(c));
+        }
+        match s.to_lowercase().as_str() {
+            "up" => Ok(KeyAction::Up),
+            "down" => Ok(KeyAction::Down),
+            "left" => Ok(KeyAction::Left),
+            "right" => Ok(KeyAction::Right),
+            "enter" => Ok(KeyAction::Enter),
+            "escape" | "esc" => Ok(KeyAction::Escape),
+            "backspace" => Ok(KeyAction::Backspace),
+            "delete" => Ok(KeyAction::Delete),
+            "tab" => Ok(KeyAction::Tab),
+            "backtab" => Ok(KeyAction::BackTab),
+            "home" => Ok(KeyAction::Home),
+            "end" => Ok(KeyAction::End),
+            "space" => Ok(KeyAction::Char(' ')),
+            _ => Err(format!(
+                "unknown key `{}`, expected a single character, space, up, down, left, right, enter, escape, \
+                backspace, delete, tab, backtab, home or end", s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for KeyAction {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
@@ -0,0 +1,90 @@
+use crossterm::Result;
+use uuid::Uuid;
+use crate::screen::dimension::Dimension;
+use crate::screen::style::Style;
+use crate::screen::text;
+use crate::screen::window::component::Component;
+use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::mouse_action::MouseAction;
+use crate::screen::window::update_element::UpdateElement;
+
+// Text that does nothing when clicked.
+#[derive(Debug, Clone)]
+pub struct LabelComponent {
+    id: Uuid,
+    text: Box<str>,
+    style: Style,
+    changed: bool,
+}
+
+impl LabelComponent {
+    pub fn new(text: Box<str>) -> Self {
+        LabelComponent::new_with_style(text, Style::default())
+    }
+
+    pub fn new_with_style(text: Box<str>, style: Style) -> Self {
+        LabelComponent {
+            id: Uuid::new_v4(),
+            text,
+            style,
+            changed: true,
+        }
+    }
+
+    // The window has to be drawn again when the width changes.
+    pub fn set_text(&mut self, text: Box<str>) {
+        self.text = text;
+        self.changed = true;
+    }
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for LabelComponent {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        (text::get_width(&self.text), 1).into()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        self.changed = false;
+        Ok(text::layout(&self.text, (0, 0).into(), text::get_width(&self.text), self.style))
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        match self.changed {
+            true => Component::<T>::get_state(self),
+            false => Ok(vec![]),
+        }
+    }
+
+    fn handle_click(&mut self, _click: MouseAction) -> Result<Vec<T>> {
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{draw_component, get_line, TestAction};
+
+    #[test]
+    fn text_is_drawn_at_its_width() {
+        let label = LabelComponent::new("Größe 🙂".into());
+        assert_eq!(Component::<TestAction>::get_size(&label), (8, 1).into());
+        let screen = draw_component(label);
+        assert_eq!(get_line(&screen, 0), "Größe 🙂");
+    }
+
+    #[test]
+    fn new_text_is_drawn_again() {
+        let mut label = LabelComponent::new("Mines".into());
+        Component::<TestAction>::get_state(&mut label).unwrap();
+        assert!(Component::<TestAction>::get_updates(&mut label).unwrap().is_empty());
+        label.set_text("Flags".into());
+        let updates = Component::<TestAction>::get_updates(&mut label).unwrap();
+        let text: String = updates.into_iter().map(|update| update.value).collect();
+        assert_eq!(text, "Flags");
+    }
+}
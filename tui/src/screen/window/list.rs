@@ -0,0 +1,207 @@
+use crossterm::Result;
+use uuid::Uuid;
+use crate::screen::dimension::Dimension;
+use crate::screen::style::Style;
+use crate::screen::text;
+use crate::screen::theme::Theme;
+use crate::screen::window::component::Component;
+use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::key_action::KeyAction;
+use crate::screen::window::mouse_action::MouseAction;
+use crate::screen::window::update_element::UpdateElement;
+
+// Items in rows, of which one is selected. Items that do not fit in the height scroll into view
+// as the selection moves with Up, Down, Home and End, or by left clicking an item. Enter or a
+// double click gives the submit action, if there is one.
+#[derive(Debug, Clone)]
+pub struct ListComponent<T: HasCloseAndRefreshActions + PartialEq + Clone> {
+    id: Uuid,
+    items: Vec<String>,
+    selected: usize,
+    // Index of the item in the top row.
+    scroll: usize,
+    size: Dimension,
+    selected_style: Style,
+    changed: bool,
+    submit_action: Option<T>,
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> ListComponent<T> {
+    pub fn new(items: Vec<String>, size: Dimension, submit_action: Option<T>) -> Self {
+        ListComponent {
+            id: Uuid::new_v4(),
+            items,
+            selected: 0,
+            scroll: 0,
+            size,
+            selected_style: Style::default(),
+            changed: true,
+            submit_action,
+        }
+    }
+
+    // None when there are no items.
+    pub fn get_selected(&self) -> Option<usize> {
+        match self.items.is_empty() {
+            true => None,
+            false => Some(self.selected),
+        }
+    }
+
+    pub fn set_selected(&mut self, selected: usize) {
+        self.selected = selected.min(self.items.len().saturating_sub(1));
+        let height = self.size.height.max(1) as usize;
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + height {
+            self.scroll = self.selected + 1 - height;
+        }
+        self.changed = true;
+    }
+
+    // Keeps the same index selected, as far as there are items.
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.items = items;
+        self.scroll = 0;
+        self.set_selected(self.selected);
+    }
+
+    fn submit(&self) -> Vec<T> {
+        match self.items.is_empty() {
+            true => vec![],
+            false => self.submit_action.clone().into_iter().collect(),
+        }
+    }
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for ListComponent<T> {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        self.size
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        self.changed = false;
+        let width = self.size.width;
+        Ok((0..self.size.height).flat_map(|y| {
+            let idx = self.scroll + y as usize;
+            let item = self.items.get(idx).map(String::as_str).unwrap_or("");
+            let style = match idx == self.selected && idx < self.items.len() {
+                true => self.selected_style,
+                false => Style::default(),
+            };
+            text::layout(&text::fit(item, width), (0, y).into(), width, style)
+        }).collect())
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        match self.changed {
+            true => self.get_state(),
+            false => Ok(vec![]),
+        }
+    }
+
+    fn handle_click(&mut self, click: MouseAction) -> Result<Vec<T>> {
+        let y = click.to_point().y;
+        let idx = self.scroll + y.max(0) as usize;
+        if y < 0 || idx >= self.items.len() {
+            return Ok(vec![]);
+        }
+        Ok(match click {
+            MouseAction::Left(_) => {
+                self.set_selected(idx);
+                vec![]
+            }
+            MouseAction::Double(_) => {
+                self.set_selected(idx);
+                self.submit()
+            }
+            _ => vec![],
+        })
+    }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<T>>> {
+        match key {
+            KeyAction::Up => self.set_selected(self.selected.saturating_sub(1)),
+            KeyAction::Down => self.set_selected(self.selected + 1),
+            KeyAction::Home => self.set_selected(0),
+            KeyAction::End => self.set_selected(self.items.len()),
+            KeyAction::Enter => return Ok(Some(self.submit())),
+            _ => return Ok(None),
+        }
+        Ok(Some(vec![]))
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.selected_style = theme.selected;
+        self.changed = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{draw_component, get_line, TestAction};
+
+    // Five items in two rows.
+    fn list() -> ListComponent<TestAction> {
+        let items = (0..5).map(|idx| format!("item {}", idx)).collect();
+        ListComponent::new(items, (8, 2).into(), Some(TestAction::Clicked(1)))
+    }
+
+    #[test]
+    fn selection_stops_at_the_first_and_last_item() {
+        let mut list = list();
+        list.handle_key(KeyAction::Up).unwrap();
+        assert_eq!(list.get_selected(), Some(0));
+        list.handle_key(KeyAction::End).unwrap();
+        assert_eq!(list.get_selected(), Some(4));
+        list.handle_key(KeyAction::Down).unwrap();
+        assert_eq!(list.get_selected(), Some(4));
+        list.handle_key(KeyAction::Home).unwrap();
+        assert_eq!(list.get_selected(), Some(0));
+        assert_eq!(list.handle_key(KeyAction::Left).unwrap(), None);
+    }
+
+    #[test]
+    fn enter_and_double_click_submit() {
+        let mut list = list();
+        assert_eq!(list.handle_key(KeyAction::Enter).unwrap(), Some(vec![TestAction::Clicked(1)]));
+        assert_eq!(list.handle_click(MouseAction::Left((0, 1).into())).unwrap(), vec![]);
+        assert_eq!(list.get_selected(), Some(1));
+        assert_eq!(list.handle_click(MouseAction::Double((0, 0).into())).unwrap(), vec![TestAction::Clicked(1)]);
+        assert_eq!(list.get_selected(), Some(0));
+
+        let mut empty_list = ListComponent::new(vec![], (8, 2).into(), Some(TestAction::Clicked(1)));
+        assert_eq!(empty_list.get_selected(), None);
+        assert_eq!(empty_list.handle_key(KeyAction::Enter).unwrap(), Some(vec![]));
+    }
+
+    #[test]
+    fn fewer_items_keep_the_selection_on_the_last_one() {
+        let mut list = list();
+        list.set_selected(4);
+        list.set_items(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(list.get_selected(), Some(1));
+    }
+
+    #[test]
+    fn selection_scrolls_into_view() {
+        let mut screen = draw_component(list());
+        assert_eq!(get_line(&screen, 0), "item 0");
+        assert_eq!(get_line(&screen, 1), "item 1");
+        screen.handle_key(KeyAction::Down).unwrap();
+        screen.handle_key(KeyAction::Down).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_line(&screen, 0), "item 1");
+        assert_eq!(get_line(&screen, 1), "item 2");
+        screen.handle_key(KeyAction::End).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_line(&screen, 0), "item 3");
+        assert_eq!(get_line(&screen, 1), "item 4");
+        assert_eq!(get_line(&screen, 2), "");
+    }
+}
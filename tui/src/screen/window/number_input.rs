@@ -0,0 +1,279 @@
+use crossterm::Result;
+use uuid::Uuid;
+use crate::screen::dimension::Dimension;
+use crate::screen::style::Style;
+use crate::screen::text;
+use crate::screen::theme::Theme;
+use crate::screen::window::component::Component;
+use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::key_action::KeyAction;
+use crate::screen::window::mouse_action::MouseAction;
+use crate::screen::window::update_element::UpdateElement;
+
+const DECREASE_LABEL: &str = "[-]";
+const INCREASE_LABEL: &str = "[+]";
+// Right clicks change the value by this much.
+const BIG_STEP: i32 = 10;
+
+// A whole number between min and max, drawn as "[-] 003 [+]". Left clicking [-] or [+] changes
+// it by 1 and right clicking by 10. With the keyboard, Left or - and Right or + change it by 1,
+// and Home and End go to min and max. Digits typed in a row replace the value and Backspace
+// takes the last one off. Typed digits are only kept between min and max once they are
+// committed, by another key, a click or commit, so 12 can be typed when min is 10.
+#[derive(Debug, Clone)]
+pub struct NumberInputComponent<T: HasCloseAndRefreshActions + PartialEq + Clone> {
+    id: Uuid,
+    value: i32,
+    min: i32,
+    max: i32,
+    // Columns of the value, enough for min and max.
+    digits: i32,
+    // The digits typed so far, while they are not committed yet.
+    typed: Option<String>,
+    style: Style,
+    changed: bool,
+    change_action: Option<T>,
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> NumberInputComponent<T> {
+    pub fn new(value: i32, min: i32, max: i32, change_action: Option<T>) -> Self {
+        let digits = min.to_string().len().max(max.to_string().len()) as i32;
+        NumberInputComponent {
+            id: Uuid::new_v4(),
+            value: value.clamp(min, max),
+            min,
+            max,
+            digits,
+            typed: None,
+            style: Style::default(),
+            changed: true,
+            change_action,
+        }
+    }
+
+    // What the value is once the typed digits are committed.
+    pub fn get_value(&self) -> i32 {
+        match self.typed.as_deref() {
+            None | Some("") => self.value,
+            Some(typed) => typed.parse::<i32>().unwrap_or(self.max).clamp(self.min, self.max),
+        }
+    }
+
+    pub fn set_value(&mut self, value: i32) {
+        self.value = value.clamp(self.min, self.max);
+        self.typed = None;
+        self.changed = true;
+    }
+
+    // Keeps the typed digits between min and max, for when the focus leaves the input.
+    // Nothing typed leaves the value as it was.
+    pub fn commit(&mut self) {
+        if self.typed.is_some() {
+            self.value = self.get_value();
+            self.typed = None;
+            self.changed = true;
+        }
+    }
+
+    fn get_increase_x(&self) -> i32 {
+        text::get_width(DECREASE_LABEL) + self.digits + 2
+    }
+
+    // The change action when the value changed, so the owner can react to it.
+    fn change_by(&mut self, amount: i32) -> Vec<T> {
+        self.commit();
+        self.change_to(self.value.saturating_add(amount))
+    }
+
+    fn change_to(&mut self, value: i32) -> Vec<T> {
+        let old_value = self.get_value();
+        self.commit();
+        self.value = value.clamp(self.min, self.max);
+        self.get_change_actions(old_value)
+    }
+
+    // Starts again from the digit once there are as many digits as fit.
+    fn type_digit(&mut self, digit: char) -> Vec<T> {
+        let old_value = self.get_value();
+        let mut typed = self.typed.take().unwrap_or_default();
+        if typed.len() >= self.digits as usize || typed == "0" {
+            typed.clear();
+        }
+        typed.push(digit);
+        self.typed = Some(typed);
+        self.get_change_actions(old_value)
+    }
+
+    // Starts from the value when nothing is typed yet.
+    fn remove_digit(&mut self) -> Vec<T> {
+        let old_value = self.get_value();
+        let mut typed = self.typed.take().unwrap_or_else(|| self.value.to_string());
+        typed.pop();
+        self.typed = Some(typed);
+        self.get_change_actions(old_value)
+    }
+
+    fn get_change_actions(&mut self, old_value: i32) -> Vec<T> {
+        self.changed = true;
+        match self.get_value() == old_value {
+            true => vec![],
+            false => self.change_action.clone().into_iter().collect(),
+        }
+    }
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for NumberInputComponent<T> {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        (self.get_increase_x() + text::get_width(INCREASE_LABEL), 1).into()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        self.changed = false;
+        let mut updates = text::layout(DECREASE_LABEL, (0, 0).into(), text::get_width(DECREASE_LABEL), Style::default());
+        let value = match &self.typed {
+            Some(typed) => format!("{:>w$}", typed, w = self.digits as usize),
+            None => format!("{:0w$}", self.value, w = self.digits as usize),
+        };
+        let value_x = text::get_width(DECREASE_LABEL) + 1;
+        updates.append(&mut text::layout(&value, (value_x, 0).into(), self.digits, self.style));
+        updates.append(&mut text::layout(INCREASE_LABEL, (self.get_increase_x(), 0).into(), text::get_width(INCREASE_LABEL), Style::default()));
+        Ok(updates)
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        match self.changed {
+            true => self.get_state(),
+            false => Ok(vec![]),
+        }
+    }
+
+    fn handle_click(&mut self, click: MouseAction) -> Result<Vec<T>> {
+        let step = match click {
+            MouseAction::Left(_) => 1,
+            MouseAction::Right(_) => BIG_STEP,
+            _ => return Ok(vec![]),
+        };
+        let x = click.to_point().x;
+        if (0..text::get_width(DECREASE_LABEL)).contains(&x) {
+            return Ok(self.change_by(-step));
+        }
+        let increase_x = self.get_increase_x();
+        if (increase_x..increase_x + text::get_width(INCREASE_LABEL)).contains(&x) {
+            return Ok(self.change_by(step));
+        }
+        Ok(vec![])
+    }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<T>>> {
+        Ok(Some(match key {
+            KeyAction::Left | KeyAction::Char('-') => self.change_by(-1),
+            KeyAction::Right | KeyAction::Char('+') => self.change_by(1),
+            KeyAction::Home => self.change_to(self.min),
+            KeyAction::End => self.change_to(self.max),
+            KeyAction::Char(c) if c.is_ascii_digit() => self.type_digit(c),
+            KeyAction::Backspace => self.remove_digit(),
+            _ => {
+                self.commit();
+                return Ok(None);
+            }
+        }))
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.style = theme.input;
+        self.changed = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{draw_component, get_line, TestAction};
+
+    fn type_keys(input: &mut NumberInputComponent<TestAction>, keys: &str) {
+        for c in keys.chars() {
+            input.handle_key(KeyAction::Char(c)).unwrap();
+        }
+    }
+
+    #[test]
+    fn values_above_nine_can_be_typed_with_a_minimum_of_ten() {
+        let mut input = NumberInputComponent::<TestAction>::new(50, 10, 99, None);
+        type_keys(&mut input, "1");
+        assert_eq!(input.get_value(), 10);
+        type_keys(&mut input, "2");
+        input.commit();
+        assert_eq!(input.get_value(), 12);
+    }
+
+    #[test]
+    fn typed_value_is_clamped_when_committed() {
+        let mut input = NumberInputComponent::<TestAction>::new(5, 1, 50, None);
+        type_keys(&mut input, "99");
+        assert_eq!(input.get_value(), 50);
+        input.commit();
+        assert_eq!(input.get_value(), 50);
+        // A full field starts again from the next digit.
+        type_keys(&mut input, "123");
+        assert_eq!(input.get_value(), 3);
+    }
+
+    #[test]
+    fn backspace_takes_off_the_last_digit() {
+        let mut input = NumberInputComponent::<TestAction>::new(123, 1, 999, None);
+        input.handle_key(KeyAction::Backspace).unwrap();
+        assert_eq!(input.get_value(), 12);
+        input.handle_key(KeyAction::Backspace).unwrap();
+        input.handle_key(KeyAction::Backspace).unwrap();
+        // Nothing typed leaves the value as it was.
+        input.commit();
+        assert_eq!(input.get_value(), 123);
+    }
+
+    #[test]
+    fn other_keys_commit_the_typed_digits_first() {
+        let mut input = NumberInputComponent::<TestAction>::new(20, 10, 99, None);
+        type_keys(&mut input, "5");
+        input.handle_key(KeyAction::Right).unwrap();
+        assert_eq!(input.get_value(), 11);
+        type_keys(&mut input, "42");
+        input.handle_key(KeyAction::Home).unwrap();
+        assert_eq!(input.get_value(), 10);
+        input.handle_key(KeyAction::End).unwrap();
+        assert_eq!(input.get_value(), 99);
+        assert!(input.handle_key(KeyAction::Escape).unwrap().is_none());
+    }
+
+    #[test]
+    fn clicks_change_the_value_by_one_or_ten_within_the_range() {
+        let mut input = NumberInputComponent::<TestAction>::new(5, 1, 20, Some(TestAction::Refresh));
+        let increase_x = input.get_increase_x();
+        assert_eq!(input.handle_click(MouseAction::Left((increase_x, 0).into())).unwrap(), vec![TestAction::Refresh]);
+        assert_eq!(input.get_value(), 6);
+        input.handle_click(MouseAction::Right((increase_x + 1, 0).into())).unwrap();
+        assert_eq!(input.get_value(), 16);
+        input.handle_click(MouseAction::Right((increase_x, 0).into())).unwrap();
+        assert_eq!(input.get_value(), 20);
+        // Nothing changes at the maximum, so there is no change action.
+        assert_eq!(input.handle_click(MouseAction::Left((increase_x, 0).into())).unwrap(), vec![]);
+        input.handle_click(MouseAction::Left((0, 0).into())).unwrap();
+        assert_eq!(input.get_value(), 19);
+    }
+
+    #[test]
+    fn typed_digits_are_drawn_until_committed() {
+        let mut screen = draw_component(NumberInputComponent::new(7, 1, 999, None));
+        assert_eq!(get_line(&screen, 0), "[-] 007 [+]");
+        screen.handle_key(KeyAction::Char('4')).unwrap();
+        screen.handle_key(KeyAction::Char('2')).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_line(&screen, 0), "[-]  42 [+]");
+        screen.handle_key(KeyAction::Escape).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_line(&screen, 0), "[-] 042 [+]");
+    }
+}
@@ -0,0 +1,113 @@
+use crossterm::Result;
+use uuid::Uuid;
+use crate::screen::dimension::Dimension;
+use crate::screen::style::Style;
+use crate::screen::theme::Theme;
+use crate::screen::window::component::Component;
+use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::mouse_action::MouseAction;
+use crate::screen::window::update_element::UpdateElement;
+
+// Blocks filling an eighth of a cell more each, so the bar moves smoothly.
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+const FULL_BLOCK: char = '█';
+
+// A bar that fills from the left as the progress goes from 0 to 1.
+#[derive(Debug, Clone)]
+pub struct ProgressBarComponent {
+    id: Uuid,
+    width: i32,
+    progress: f64,
+    style: Style,
+    changed: bool,
+}
+
+impl ProgressBarComponent {
+    pub fn new(width: i32) -> Self {
+        ProgressBarComponent {
+            id: Uuid::new_v4(),
+            width,
+            progress: 0.0,
+            style: Style::default(),
+            changed: true,
+        }
+    }
+
+    pub fn get_progress(&self) -> f64 {
+        self.progress
+    }
+
+    // Kept between 0 and 1.
+    pub fn set_progress(&mut self, progress: f64) {
+        let progress = progress.clamp(0.0, 1.0);
+        if progress != self.progress {
+            self.progress = progress;
+            self.changed = true;
+        }
+    }
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for ProgressBarComponent {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        (self.width, 1).into()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        self.changed = false;
+        let eighths = (self.progress * self.width as f64 * 8.0).round() as i32;
+        Ok((0..self.width).map(|x| {
+            let value = match eighths - x * 8 {
+                filled if filled >= 8 => FULL_BLOCK,
+                filled if filled > 0 => PARTIAL_BLOCKS[filled as usize],
+                _ => ' ',
+            };
+            UpdateElement { point: (x, 0).into(), value: value.to_string(), style: self.style }
+        }).collect())
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        match self.changed {
+            true => Component::<T>::get_state(self),
+            false => Ok(vec![]),
+        }
+    }
+
+    fn handle_click(&mut self, _click: MouseAction) -> Result<Vec<T>> {
+        Ok(vec![])
+    }
+
+    // The empty part of the bar has the background of an input field.
+    fn set_theme(&mut self, theme: &Theme) {
+        self.style = theme.input;
+        self.changed = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{draw_component, get_line};
+
+    #[test]
+    fn progress_is_kept_between_zero_and_one() {
+        let mut progress_bar = ProgressBarComponent::new(4);
+        progress_bar.set_progress(1.5);
+        assert_eq!(progress_bar.get_progress(), 1.0);
+        progress_bar.set_progress(-1.0);
+        assert_eq!(progress_bar.get_progress(), 0.0);
+    }
+
+    #[test]
+    fn bar_fills_by_eighths_of_a_cell() {
+        let mut progress_bar = ProgressBarComponent::new(4);
+        progress_bar.set_progress(0.5625);
+        assert_eq!(get_line(&draw_component(progress_bar), 0), "██▎");
+        let mut progress_bar = ProgressBarComponent::new(4);
+        progress_bar.set_progress(1.0);
+        assert_eq!(get_line(&draw_component(progress_bar), 0), "████");
+    }
+}
@@ -0,0 +1,148 @@
+use crossterm::Result;
+use uuid::Uuid;
+use crate::screen::dimension::Dimension;
+use crate::screen::style::Style;
+use crate::screen::text;
+use crate::screen::window::component::Component;
+use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::key_action::KeyAction;
+use crate::screen::window::mouse_action::MouseAction;
+use crate::screen::window::update_element::UpdateElement;
+
+// One option on each row, of which exactly one is picked, drawn as "(•) option". An option is
+// picked by left clicking it, or with Up and Down.
+#[derive(Debug, Clone)]
+pub struct RadioGroupComponent<T: HasCloseAndRefreshActions + PartialEq + Clone> {
+    id: Uuid,
+    options: Vec<Box<str>>,
+    selected: usize,
+    changed: bool,
+    change_action: Option<T>,
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> RadioGroupComponent<T> {
+    pub fn new(options: Vec<Box<str>>, selected: usize, change_action: Option<T>) -> Self {
+        let selected = selected.min(options.len().saturating_sub(1));
+        RadioGroupComponent {
+            id: Uuid::new_v4(),
+            options,
+            selected,
+            changed: true,
+            change_action,
+        }
+    }
+
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn set_selected(&mut self, selected: usize) {
+        if selected < self.options.len() {
+            self.selected = selected;
+            self.changed = true;
+        }
+    }
+
+    fn select(&mut self, selected: usize) -> Vec<T> {
+        if selected == self.selected || selected >= self.options.len() {
+            return vec![];
+        }
+        self.set_selected(selected);
+        self.change_action.clone().into_iter().collect()
+    }
+
+    fn get_line(&self, idx: usize) -> String {
+        format!("({}) {}", if idx == self.selected { '•' } else { ' ' }, self.options[idx])
+    }
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for RadioGroupComponent<T> {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        let width = (0..self.options.len()).map(|idx| text::get_width(&self.get_line(idx))).max().unwrap_or(0);
+        (width, self.options.len() as i32).into()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        self.changed = false;
+        let width = self.get_size().width;
+        Ok((0..self.options.len()).flat_map(|idx| {
+            text::layout(&text::fit(&self.get_line(idx), width), (0, idx as i32).into(), width, Style::default())
+        }).collect())
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        match self.changed {
+            true => self.get_state(),
+            false => Ok(vec![]),
+        }
+    }
+
+    fn handle_click(&mut self, click: MouseAction) -> Result<Vec<T>> {
+        Ok(match click {
+            MouseAction::Left(p) if p.y >= 0 => self.select(p.y as usize),
+            _ => vec![],
+        })
+    }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<T>>> {
+        Ok(match key {
+            KeyAction::Up if self.selected > 0 => Some(self.select(self.selected - 1)),
+            KeyAction::Down => Some(self.select(self.selected + 1)),
+            KeyAction::Up => Some(vec![]),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{draw_component, get_line, TestAction};
+
+    fn radio_group(selected: usize) -> RadioGroupComponent<TestAction> {
+        RadioGroupComponent::new(vec!["Easy".into(), "Medium".into(), "Hard".into()], selected, Some(TestAction::Refresh))
+    }
+
+    #[test]
+    fn selection_is_kept_to_the_options() {
+        assert_eq!(radio_group(5).get_selected(), 2);
+        let mut radio_group = radio_group(0);
+        radio_group.set_selected(3);
+        assert_eq!(radio_group.get_selected(), 0);
+    }
+
+    #[test]
+    fn up_and_down_stop_at_the_first_and_last_option() {
+        let mut radio_group = radio_group(0);
+        assert_eq!(radio_group.handle_key(KeyAction::Up).unwrap(), Some(vec![]));
+        assert_eq!(radio_group.get_selected(), 0);
+        assert_eq!(radio_group.handle_key(KeyAction::Down).unwrap(), Some(vec![TestAction::Refresh]));
+        radio_group.handle_key(KeyAction::Down).unwrap();
+        assert_eq!(radio_group.handle_key(KeyAction::Down).unwrap(), Some(vec![]));
+        assert_eq!(radio_group.get_selected(), 2);
+        assert_eq!(radio_group.handle_key(KeyAction::Left).unwrap(), None);
+    }
+
+    #[test]
+    fn clicked_option_is_picked() {
+        let mut radio_group = radio_group(2);
+        assert_eq!(radio_group.handle_click(MouseAction::Left((3, 0).into())).unwrap(), vec![TestAction::Refresh]);
+        assert_eq!(radio_group.get_selected(), 0);
+        assert_eq!(radio_group.handle_click(MouseAction::Left((0, 3).into())).unwrap(), vec![]);
+        assert_eq!(radio_group.get_selected(), 0);
+    }
+
+    #[test]
+    fn picked_option_is_drawn() {
+        let mut screen = draw_component(radio_group(1));
+        assert_eq!((0..3).map(|y| get_line(&screen, y)).collect::<Vec<_>>(), vec!["( ) Easy", "(•) Medium", "( ) Hard"]);
+        screen.handle_key(KeyAction::Down).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_line(&screen, 1), "( ) Medium");
+        assert_eq!(get_line(&screen, 2), "(•) Hard");
+    }
+}
@@ -0,0 +1,93 @@
+use crossterm::Result;
+use uuid::Uuid;
+use crate::screen::dimension::Dimension;
+use crate::screen::style::Style;
+use crate::screen::text;
+use crate::screen::window::component::Component;
+use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::mouse_action::MouseAction;
+use crate::screen::window::update_element::UpdateElement;
+
+// Text over several lines, wrapped between words to the width. It is as tall as its lines.
+#[derive(Debug, Clone)]
+pub struct TextBlockComponent {
+    id: Uuid,
+    width: i32,
+    lines: Vec<String>,
+    style: Style,
+    changed: bool,
+}
+
+impl TextBlockComponent {
+    pub fn new(text: &str, width: i32) -> Self {
+        TextBlockComponent::new_with_style(text, width, Style::default())
+    }
+
+    pub fn new_with_style(text: &str, width: i32, style: Style) -> Self {
+        TextBlockComponent {
+            id: Uuid::new_v4(),
+            width,
+            lines: text::wrap(text, width),
+            style,
+            changed: true,
+        }
+    }
+
+    // The window has to be drawn again when the number of lines changes.
+    pub fn set_text(&mut self, text: &str) {
+        self.lines = text::wrap(text, self.width);
+        self.changed = true;
+    }
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for TextBlockComponent {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        (self.width, self.lines.len() as i32).into()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        self.changed = false;
+        Ok(self.lines.iter().enumerate().flat_map(|(y, line)| {
+            text::layout(&text::fit(line, self.width), (0, y as i32).into(), self.width, self.style)
+        }).collect())
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        match self.changed {
+            true => Component::<T>::get_state(self),
+            false => Ok(vec![]),
+        }
+    }
+
+    fn handle_click(&mut self, _click: MouseAction) -> Result<Vec<T>> {
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{draw_component, get_line, TestAction};
+
+    #[test]
+    fn text_is_wrapped_between_words() {
+        let text_block = TextBlockComponent::new("The quick brown fox", 9);
+        assert_eq!(Component::<TestAction>::get_size(&text_block), (9, 2).into());
+        let screen = draw_component(text_block);
+        assert_eq!(get_line(&screen, 0), "The quick");
+        assert_eq!(get_line(&screen, 1), "brown fox");
+    }
+
+    #[test]
+    fn new_text_changes_the_height() {
+        let mut text_block = TextBlockComponent::new("The quick brown fox", 9);
+        text_block.set_text("Jumps\nover");
+        assert_eq!(Component::<TestAction>::get_size(&text_block), (9, 2).into());
+        text_block.set_text("Jumps");
+        assert_eq!(Component::<TestAction>::get_size(&text_block), (9, 1).into());
+    }
+}
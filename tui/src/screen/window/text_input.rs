@@ -0,0 +1,228 @@
+use crossterm::Result;
+use unicode_width::UnicodeWidthChar;
+use uuid::Uuid;
+use crate::screen::dimension::Dimension;
+use crate::screen::theme::Theme;
+use crate::screen::window::component::Component;
+use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+use crate::screen::window::key_action::KeyAction;
+use crate::screen::window::mouse_action::MouseAction;
+use crate::screen::window::update_element::UpdateElement;
+
+// One line of editable text. Characters are typed in at the cursor, and the text scrolls
+// when it is longer than the field. Enter gives the submit action, if there is one.
+#[derive(Debug, Clone)]
+pub struct TextInputComponent<T: HasCloseAndRefreshActions + PartialEq + Clone> {
+    id: Uuid,
+    value: Vec<char>,
+    // Index in value the next character is typed in at.
+    cursor: usize,
+    width: i32,
+    max_len: usize,
+    changed: bool,
+    submit_action: Option<T>,
+    theme: Theme,
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> TextInputComponent<T> {
+    pub fn new(width: i32, max_len: usize, submit_action: Option<T>) -> Self {
+        TextInputComponent {
+            id: Uuid::new_v4(),
+            value: vec![],
+            cursor: 0,
+            width,
+            max_len,
+            changed: true,
+            submit_action,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn get_value(&self) -> String {
+        self.value.iter().collect()
+    }
+
+    // Only single column characters are kept, up to max_len of them. The cursor goes to the end.
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value.chars().filter(|c| c.width() == Some(1)).take(self.max_len).collect();
+        self.cursor = self.value.len();
+        self.changed = true;
+    }
+
+    // The first character shown, so the cursor is always in the field.
+    fn get_scroll(&self) -> usize {
+        (self.cursor + 1).saturating_sub(self.width as usize)
+    }
+
+    fn insert(&mut self, c: char) -> bool {
+        if self.value.len() >= self.max_len || c.width() != Some(1) {
+            return false;
+        }
+        self.value.insert(self.cursor, c);
+        self.cursor += 1;
+        true
+    }
+}
+
+impl<T: HasCloseAndRefreshActions + PartialEq + Clone> Component<T> for TextInputComponent<T> {
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_size(&self) -> Dimension {
+        (self.width, 1).into()
+    }
+
+    fn get_state(&mut self) -> Result<Vec<UpdateElement>> {
+        self.changed = false;
+        let scroll = self.get_scroll();
+        Ok((0..self.width as usize).map(|x| {
+            let idx = scroll + x;
+            UpdateElement {
+                point: (x as i32, 0).into(),
+                value: self.value.get(idx).copied().unwrap_or(' ').to_string(),
+                style: match idx == self.cursor {
+                    true => self.theme.input_cursor,
+                    false => self.theme.input,
+                },
+            }
+        }).collect())
+    }
+
+    fn get_updates(&mut self) -> Result<Vec<UpdateElement>> {
+        match self.changed {
+            true => Component::<T>::get_state(self),
+            false => Ok(vec![]),
+        }
+    }
+
+    fn handle_click(&mut self, click: MouseAction) -> Result<Vec<T>> {
+        if let MouseAction::Left(p) = click {
+            self.cursor = (self.get_scroll() + p.x.max(0) as usize).min(self.value.len());
+            self.changed = true;
+        }
+        Ok(vec![])
+    }
+
+    fn handle_key(&mut self, key: KeyAction) -> Result<Option<Vec<T>>> {
+        match key {
+            KeyAction::Char(c) => {
+                if !self.insert(c) {
+                    return Ok(Some(vec![]));
+                }
+            }
+            KeyAction::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.value.remove(self.cursor);
+            }
+            KeyAction::Delete if self.cursor < self.value.len() => {
+                self.value.remove(self.cursor);
+            }
+            KeyAction::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyAction::Right => self.cursor = (self.cursor + 1).min(self.value.len()),
+            KeyAction::Home => self.cursor = 0,
+            KeyAction::End => self.cursor = self.value.len(),
+            KeyAction::Backspace | KeyAction::Delete => {}
+            KeyAction::Enter => return Ok(Some(self.submit_action.clone().into_iter().collect())),
+            _ => return Ok(None),
+        }
+        self.changed = true;
+        Ok(Some(vec![]))
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+        self.changed = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{draw_component, get_line, TestAction};
+
+    fn type_text(input: &mut TextInputComponent<TestAction>, text: &str) {
+        for c in text.chars() {
+            input.handle_key(KeyAction::Char(c)).unwrap();
+        }
+    }
+
+    #[test]
+    fn characters_go_in_at_the_cursor() {
+        let mut input = TextInputComponent::<TestAction>::new(10, 16, None);
+        type_text(&mut input, "ace");
+        input.handle_key(KeyAction::Left).unwrap();
+        type_text(&mut input, "d");
+        input.handle_key(KeyAction::Home).unwrap();
+        type_text(&mut input, "_");
+        input.handle_key(KeyAction::End).unwrap();
+        type_text(&mut input, "!");
+        assert_eq!(input.get_value(), "_acde!");
+    }
+
+    #[test]
+    fn backspace_and_delete_remove_around_the_cursor() {
+        let mut input = TextInputComponent::<TestAction>::new(10, 16, None);
+        input.set_value("abcd");
+        input.handle_key(KeyAction::Backspace).unwrap();
+        assert_eq!(input.get_value(), "abc");
+        input.handle_key(KeyAction::Delete).unwrap();
+        assert_eq!(input.get_value(), "abc");
+        input.handle_key(KeyAction::Home).unwrap();
+        input.handle_key(KeyAction::Backspace).unwrap();
+        input.handle_key(KeyAction::Delete).unwrap();
+        assert_eq!(input.get_value(), "bc");
+        assert_eq!(input.cursor, 0);
+    }
+
+    #[test]
+    fn text_stops_at_the_max_len() {
+        let mut input = TextInputComponent::<TestAction>::new(10, 4, None);
+        type_text(&mut input, "abcdef");
+        assert_eq!(input.get_value(), "abcd");
+        input.set_value("123456");
+        assert_eq!(input.get_value(), "1234");
+    }
+
+    #[test]
+    fn wide_characters_are_left_out() {
+        let mut input = TextInputComponent::<TestAction>::new(10, 16, None);
+        type_text(&mut input, "a🙂b界");
+        assert_eq!(input.get_value(), "ab");
+        input.set_value("x🙂y");
+        assert_eq!(input.get_value(), "xy");
+    }
+
+    #[test]
+    fn enter_gives_the_submit_action() {
+        let mut input = TextInputComponent::new(10, 16, Some(TestAction::Clicked(1)));
+        assert_eq!(input.handle_key(KeyAction::Enter).unwrap(), Some(vec![TestAction::Clicked(1)]));
+        assert_eq!(input.handle_key(KeyAction::Escape).unwrap(), None);
+    }
+
+    #[test]
+    fn long_text_scrolls_to_keep_the_cursor_in_the_field() {
+        let mut screen = draw_component(TextInputComponent::new(5, 16, None));
+        for c in "abcdefg".chars() {
+            screen.handle_key(KeyAction::Char(c)).unwrap();
+        }
+        screen.draw().unwrap();
+        // The last column is left for the cursor.
+        assert_eq!(get_line(&screen, 0), "defg");
+        screen.handle_key(KeyAction::Home).unwrap();
+        screen.draw().unwrap();
+        assert_eq!(get_line(&screen, 0), "abcde");
+    }
+
+    #[test]
+    fn click_moves_the_cursor() {
+        let mut input = TextInputComponent::<TestAction>::new(5, 16, None);
+        input.set_value("abcdefg");
+        // Scrolled by three, so the second column is the fifth character.
+        input.handle_click(MouseAction::Left((1, 0).into())).unwrap();
+        assert_eq!(input.cursor, 4);
+        input.set_value("ab");
+        input.handle_click(MouseAction::Left((4, 0).into())).unwrap();
+        assert_eq!(input.cursor, 2);
+    }
+}
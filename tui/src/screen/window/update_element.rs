@@ -1,9 +1,11 @@
-use crossterm::style::Color;
 use crate::screen::Point;
+use crate::screen::style::Style;
 
-#[derive(Debug, Copy, Clone)]
+// One cell to draw. The value is a whole grapheme, such as a letter with its combining marks or
+// an emoji sequence, so it is never split over cells.
+#[derive(Debug, Clone)]
 pub struct UpdateElement {
     pub point: Point,
-    pub value: char,
-    pub fg: Option<Color>,
+    pub value: String,
+    pub style: Style,
 }
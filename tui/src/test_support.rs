@@ -0,0 +1,39 @@
+use uuid::Uuid;
+use crate::backend::test_backend::TestBackend;
+use crate::screen::Screen;
+use crate::screen::window::Window;
+use crate::screen::window::border_style::BorderStyle;
+use crate::screen::window::component::Component;
+use crate::screen::window::has_close_action::HasCloseAndRefreshActions;
+
+// The actions of the games live in the games, so the tests of this crate have their own.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TestAction {
+    Close(Uuid),
+    Refresh,
+    Clicked(u8),
+}
+
+impl HasCloseAndRefreshActions for TestAction {
+    fn get_close_action(id: Uuid) -> Self {
+        TestAction::Close(id)
+    }
+
+    fn get_refresh_action() -> Self {
+        TestAction::Refresh
+    }
+}
+
+// A 20x6 screen with the component in the top left corner, without a border, drawn once.
+// Keys sent to the screen go to the component.
+pub(crate) fn draw_component(component: impl Component<TestAction> + 'static) -> Screen<TestAction, TestBackend> {
+    let mut screen = Screen::new_with_backend(TestBackend::new(20, 6)).unwrap();
+    screen.add(Window::new((0, 0).into(), 0, Box::new(component), BorderStyle::None, "".into(), false, false)).unwrap();
+    screen.draw().unwrap();
+    screen
+}
+
+// The drawn text of a row, without the blank cells after it.
+pub(crate) fn get_line(screen: &Screen<TestAction, TestBackend>, y: i32) -> String {
+    screen.backend().get_line(y).trim_end().to_string()
+}